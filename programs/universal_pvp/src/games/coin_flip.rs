@@ -0,0 +1,240 @@
+use anchor_lang::prelude::*;
+use crate::universal_engine::{
+    GameResult, GameConfig, GameConfigBounds, MatchOutcome, RandomnessSource, UniversalMatch,
+};
+use super::{GameContext, GameLogic};
+
+// ===========================
+// COIN FLIP MODULE
+// ===========================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum CoinSide {
+    Heads = 0,
+    Tails = 1,
+}
+
+impl CoinSide {
+    pub fn from_u8(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(CoinSide::Heads),
+            1 => Ok(CoinSide::Tails),
+            _ => Err(error!(CoinFlipError::InvalidSide)),
+        }
+    }
+    
+    pub fn opposite(&self) -> CoinSide {
+        match self {
+            CoinSide::Heads => CoinSide::Tails,
+            CoinSide::Tails => CoinSide::Heads,
+        }
+    }
+}
+
+/// Pile ou face: le créateur choisit son côté à la création du match
+/// (`custom_params[0]`), l'opposant reçoit l'autre. Le lancer vient de
+/// l'aléatoire commun des deux joueurs, il n'y a donc jamais d'égalité.
+/// À plus de deux sièges (battle royale), un seul siège survit aux lancers.
+pub struct CoinFlipGame;
+
+impl CoinFlipGame {
+    /// Index du côté du créateur dans `GameConfig::custom_params`
+    pub const CREATOR_SIDE_PARAM: usize = 0;
+    
+    /// Lance la pièce pour le round en cours
+    pub fn flip(randomness: &dyn RandomnessSource, round: u8) -> Result<CoinSide> {
+        let value = randomness.random_below(&[b"coin_flip".as_slice(), &[round]].concat(), 2)?;
+        CoinSide::from_u8(value as u8)
+    }
+    
+    /// Battle royale: siège survivant, tiré uniformément parmi `seats` sièges
+    pub fn royale_survivor(randomness: &dyn RandomnessSource, round: u8, seats: usize) -> Result<u8> {
+        let domain = [b"coin_flip_royale".as_slice(), &[round]].concat();
+        Ok(randomness.random_below(&domain, seats as u64)? as u8)
+    }
+}
+
+impl GameLogic for CoinFlipGame {
+    fn name(&self) -> &'static str {
+        "Coin Flip"
+    }
+    
+    fn validate_config(&self, config: &GameConfig, bounds: &GameConfigBounds) -> Result<()> {
+        // Le côté du créateur est un choix de jeu (pile ou face), pas une
+        // limite de table: il ne dépend pas des bornes de custom_params
+        CoinSide::from_u8(config.custom_params[Self::CREATOR_SIDE_PARAM])?;
+        
+        let mut table_config = *config;
+        table_config.custom_params[Self::CREATOR_SIDE_PARAM] = 0;
+        bounds.check(&table_config)
+    }
+    
+    fn validate_move(&self, _move_data: &[u8]) -> Result<()> {
+        // Les côtés sont fixés à la création: aucun mouvement à jouer
+        Err(CoinFlipError::NoMovesAllowed.into())
+    }
+    
+    fn move_size(&self) -> usize {
+        0
+    }
+    
+    fn max_seats(&self) -> u8 {
+        UniversalMatch::MAX_SEATS as u8
+    }
+    
+    fn initialize_state(&self, match_data: &mut UniversalMatch, _ctx: &GameContext) -> Result<()> {
+        let creator_side = CoinSide::from_u8(
+            match_data.game_config.custom_params[Self::CREATOR_SIDE_PARAM]
+        )?;
+        match_data.game_state = CoinFlipState::new(creator_side).to_bytes();
+        Ok(())
+    }
+    
+    fn can_make_move(&self, _player: &Pubkey, _match_data: &UniversalMatch) -> bool {
+        false
+    }
+    
+    fn is_round_complete(&self, match_data: &UniversalMatch, ctx: &GameContext) -> bool {
+        // Prêt dès que les seeds (et le VRF éventuel) sont révélés
+        ctx.randomness(match_data).is_ok()
+    }
+    
+    fn determine_winner(&self, match_data: &UniversalMatch, ctx: &GameContext) -> Result<GameResult> {
+        let state = CoinFlipState::from_bytes(&match_data.game_state)?;
+        let randomness = ctx.randomness(match_data)?;
+        let result = Self::flip(&randomness, match_data.current_round)?;
+        
+        msg!("🪙 Coin landed on {:?}", result);
+        
+        if result == state.creator_side {
+            Ok(GameResult::Player1Wins)
+        } else {
+            Ok(GameResult::Player2Wins)
+        }
+    }
+    
+    fn determine_outcome(&self, match_data: &UniversalMatch, ctx: &GameContext) -> Result<MatchOutcome> {
+        if match_data.seats.len() <= 2 {
+            return Ok(MatchOutcome::from_result(self.determine_winner(match_data, ctx)?));
+        }
+        
+        let randomness = ctx.randomness(match_data)?;
+        let survivor = Self::royale_survivor(&randomness, match_data.current_round, match_data.seats.len())?;
+        
+        msg!("🪙 Battle royale survivor: seat {}", survivor);
+        Ok(MatchOutcome::Winner(survivor))
+    }
+    
+    fn encode_move(&self, move_data: &[u8]) -> Vec<u8> {
+        move_data.to_vec()
+    }
+    
+    fn decode_move(&self, encoded: &[u8]) -> Result<Vec<u8>> {
+        Ok(encoded.to_vec())
+    }
+    
+    fn default_config(&self) -> GameConfig {
+        GameConfig {
+            max_players: 2,
+            min_bet: 5_000_000,         // 0.005 SOL
+            max_bet: 100_000_000_000,   // 100 SOL
+            rounds: 1,
+            custom_params: [0; 16],     // [0] = côté du créateur (0 = pile, 1 = face)
+        }
+    }
+}
+
+// ===========================
+// COIN FLIP STATE
+// ===========================
+
+/// État du pile ou face: [côté créateur, côté opposant]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct CoinFlipState {
+    pub creator_side: CoinSide,
+    pub opponent_side: CoinSide,
+}
+
+impl CoinFlipState {
+    pub fn new(creator_side: CoinSide) -> Self {
+        CoinFlipState {
+            creator_side,
+            opponent_side: creator_side.opposite(),
+        }
+    }
+    
+    pub fn to_bytes(&self) -> Vec<u8> {
+        vec![self.creator_side as u8, self.opponent_side as u8]
+    }
+    
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        require!(bytes.len() >= 2, CoinFlipError::InvalidStateData);
+        
+        let state = CoinFlipState {
+            creator_side: CoinSide::from_u8(bytes[0])?,
+            opponent_side: CoinSide::from_u8(bytes[1])?,
+        };
+        require!(
+            state.opponent_side == state.creator_side.opposite(),
+            CoinFlipError::InvalidStateData
+        );
+        
+        Ok(state)
+    }
+}
+
+// ===========================
+// COIN FLIP ERRORS
+// ===========================
+
+#[error_code]
+pub enum CoinFlipError {
+    #[msg("Invalid coin side (must be 0 = heads or 1 = tails)")]
+    InvalidSide,
+    
+    #[msg("Coin flip sides are fixed at match creation")]
+    NoMovesAllowed,
+    
+    #[msg("Invalid coin flip state data")]
+    InvalidStateData,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn creator_side_is_validated_outside_the_custom_param_bounds() {
+        // Bornes par défaut: tous les custom_params doivent rester à 0
+        let bounds = GameConfigBounds {
+            min_players: 2,
+            max_players: 2,
+            min_rounds: 1,
+            max_rounds: 1,
+            min_bet: 1,
+            max_bet: u64::MAX,
+            max_custom_params: [0; 16],
+        };
+        let mut config = GameConfig {
+            max_players: 2,
+            rounds: 1,
+            min_bet: 1,
+            max_bet: 1,
+            ..GameConfig::default()
+        };
+        
+        config.custom_params[CoinFlipGame::CREATOR_SIDE_PARAM] = CoinSide::Tails as u8;
+        assert!(CoinFlipGame.validate_config(&config, &bounds).is_ok());
+        
+        config.custom_params[CoinFlipGame::CREATOR_SIDE_PARAM] = 2;
+        assert_eq!(
+            CoinFlipGame.validate_config(&config, &bounds).unwrap_err(),
+            CoinFlipError::InvalidSide.into()
+        );
+        
+        // Les autres custom_params restent soumis aux bornes du registre
+        config.custom_params[CoinFlipGame::CREATOR_SIDE_PARAM] = 0;
+        config.custom_params[1] = 1;
+        assert!(CoinFlipGame.validate_config(&config, &bounds).is_err());
+    }
+}
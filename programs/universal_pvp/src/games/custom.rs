@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::universal_engine::{GameResult, GameConfig, UniversalGameError, UniversalMatch};
+use super::{GameContext, GameLogic};
+
+// ===========================
+// CUSTOM GAMES MODULE
+// ===========================
+
+/// Jeu custom générique: la plus haute valeur l'emporte
+pub struct CustomGame {
+    pub id: u32,
+}
+
+impl GameLogic for CustomGame {
+    fn name(&self) -> &'static str {
+        "Custom Game"
+    }
+    
+    fn validate_move(&self, move_data: &[u8]) -> Result<()> {
+        require!(!move_data.is_empty(), UniversalGameError::InvalidGameState);
+        Ok(())
+    }
+    
+    fn move_size(&self) -> usize {
+        1 // [valeur]
+    }
+    
+    fn initialize_state(&self, match_data: &mut UniversalMatch, _ctx: &GameContext) -> Result<()> {
+        match_data.game_state = vec![0; 64]; // 64 bytes génériques
+        Ok(())
+    }
+    
+    fn is_round_complete(&self, match_data: &UniversalMatch, _ctx: &GameContext) -> bool {
+        match_data.all_moves_submitted()
+    }
+    
+    fn determine_winner(&self, match_data: &UniversalMatch, _ctx: &GameContext) -> Result<GameResult> {
+        require!(match_data.game_state.len() >= 2, UniversalGameError::InvalidGameState);
+        let player1_value = match_data.game_state[0];
+        let player2_value = match_data.game_state[1];
+        
+        if player1_value > player2_value {
+            Ok(GameResult::Player1Wins)
+        } else if player2_value > player1_value {
+            Ok(GameResult::Player2Wins)
+        } else {
+            Ok(GameResult::Draw)
+        }
+    }
+    
+    fn encode_move(&self, move_data: &[u8]) -> Vec<u8> {
+        move_data.to_vec()
+    }
+    
+    fn decode_move(&self, encoded: &[u8]) -> Result<Vec<u8>> {
+        Ok(encoded.to_vec())
+    }
+    
+    fn default_config(&self) -> GameConfig {
+        GameConfig {
+            max_players: 2,
+            min_bet: 5_000_000,         // 0.005 SOL
+            max_bet: 100_000_000_000,   // 100 SOL
+            rounds: 1,
+            custom_params: [0; 16],
+        }
+    }
+}
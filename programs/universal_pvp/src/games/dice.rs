@@ -1,107 +1,182 @@
 use anchor_lang::prelude::*;
-use crate::universal_engine::{GameType, GameResult, GameConfig, UniversalMatch};
-use super::GameLogic;
+use crate::universal_engine::{
+    GameResult, GameConfig, GameConfigBounds, MatchOutcome, RandomnessSource, UniversalMatch,
+};
+use super::{GameContext, GameLogic};
 
 // ===========================
 // DICE GAME MODULE
 // ===========================
 
-/// Structure pour le jeu de dés
+/// Structure pour le jeu de dés: les dés sont lancés par le programme
+/// (`roll_dice`) depuis l'aléatoire commun, jamais fournis par les joueurs
 pub struct DiceGame;
 
-/// Résultat d'un lancer de dés
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+impl DiceGame {
+    /// Lance les dés de chaque siège pour le prochain round et les enregistre
+    /// dans le DiceGameState stocké dans game_state
+    pub fn roll_next_round(
+        match_data: &mut UniversalMatch,
+        ctx: &GameContext,
+    ) -> Result<Vec<DiceRoll>> {
+        let variant = DiceVariant::from_config(&match_data.game_config)?;
+        let randomness = ctx.randomness(match_data)?;
+        let mut state = Self::load_state(match_data)?;
+        require!(state.current_round < state.max_rounds, DiceGameError::MaxRoundsReached);
+        
+        let round = state.current_round + 1;
+        require!(match_data.seats.len() >= 2, DiceGameError::NoOpponent);
+        
+        let rolls = match variant.scoring {
+            // Over/under: un seul lancer de table, commun aux deux joueurs
+            DiceScoring::OverUnder { .. } => {
+                let table = Pubkey::default();
+                let roll = DiceRoll::new(generate_dice_rolls(&randomness, &variant, &table, round)?);
+                vec![roll; match_data.seats.len()]
+            },
+            _ => match_data
+                .seats
+                .iter()
+                .map(|seat| {
+                    Ok(DiceRoll::new(generate_dice_rolls(&randomness, &variant, &seat.player, round)?))
+                })
+                .collect::<Result<Vec<_>>>()?,
+        };
+        
+        state.add_round(rolls.clone())?;
+        
+        match_data.game_state = state.try_to_vec()?;
+        
+        Ok(rolls)
+    }
+    
+    fn load_state(match_data: &UniversalMatch) -> Result<DiceGameState> {
+        DiceGameState::try_from_slice(&match_data.game_state)
+            .map_err(|_| error!(DiceGameError::InvalidData))
+    }
+}
+
+/// Résultat d'un lancer de dés (le joueur est donné par le siège qui le stocke)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct DiceRoll {
-    pub player: Pubkey,
-    pub dice1: u8,
-    pub dice2: u8,
+    pub dice: Vec<u8>,
     pub total: u8,
 }
 
 impl DiceRoll {
-    pub fn new(player: Pubkey, dice1: u8, dice2: u8) -> Self {
+    pub fn new(dice: Vec<u8>) -> Self {
+        let total = dice.iter().sum();
         DiceRoll {
-            player,
-            dice1,
-            dice2,
-            total: dice1 + dice2,
+            dice,
+            total,
         }
     }
     
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
-        require!(bytes.len() >= 34, DiceGameError::InvalidData);
+        require!(!bytes.is_empty(), DiceGameError::InvalidData);
+        require!(bytes.iter().all(|die| *die >= 1), DiceGameError::InvalidDiceValue);
         
-        let mut player_bytes = [0u8; 32];
-        player_bytes.copy_from_slice(&bytes[0..32]);
-        let player = Pubkey::new_from_array(player_bytes);
-        
-        let dice1 = bytes[32];
-        let dice2 = bytes[33];
-        
-        Ok(DiceRoll::new(player, dice1, dice2))
+        Ok(DiceRoll::new(bytes.to_vec()))
     }
     
     pub fn to_bytes(&self) -> Vec<u8> {
-        let mut bytes = Vec::new();
-        bytes.extend_from_slice(&self.player.to_bytes());
-        bytes.push(self.dice1);
-        bytes.push(self.dice2);
-        bytes
+        self.dice.clone()
+    }
+    
+    /// Plus haut dé du lancer
+    pub fn highest(&self) -> u8 {
+        self.dice.iter().copied().max().unwrap_or(0)
+    }
+    
+    /// Au moins deux dés identiques
+    pub fn has_double(&self) -> bool {
+        self.dice
+            .iter()
+            .enumerate()
+            .any(|(i, die)| self.dice[i + 1..].contains(die))
+    }
+    
+    /// Dés triés du plus haut au plus bas, pour départager les égalités
+    pub fn sorted_desc(&self) -> Vec<u8> {
+        let mut dice = self.dice.clone();
+        dice.sort_unstable_by(|a, b| b.cmp(a));
+        dice
     }
 }
 
 impl GameLogic for DiceGame {
-    fn validate_move(&self, move_data: &[u8]) -> Result<()> {
-        require!(move_data.len() >= 2, DiceGameError::InvalidMoveData);
-        
-        let dice1 = move_data[0];
-        let dice2 = move_data[1];
-        
-        // Valider que les dés sont entre 1 et 6
-        require!(dice1 >= 1 && dice1 <= 6, DiceGameError::InvalidDiceValue);
-        require!(dice2 >= 1 && dice2 <= 6, DiceGameError::InvalidDiceValue);
-        
-        Ok(())
+    fn name(&self) -> &'static str {
+        "Dice Game"
     }
     
-    fn determine_winner(&self, match_data: &UniversalMatch) -> Result<GameResult> {
-        let game_state = &match_data.game_state;
-        
-        // Format attendu : [dice1_p1, dice2_p1, dice1_p2, dice2_p2]
-        require!(game_state.len() >= 4, DiceGameError::IncompleteGameState);
+    fn validate_config(&self, config: &GameConfig, bounds: &GameConfigBounds) -> Result<()> {
+        bounds.check(config)?;
+        let variant = DiceVariant::from_config(config)?;
         
-        // Récupérer les lancers des deux joueurs
-        let player1_roll = DiceRoll::new(
-            match_data.creator,
-            game_state[0],
-            game_state[1]
-        );
+        // Le lancer de table de l'over/under oppose exactement deux côtés
+        if let DiceScoring::OverUnder { .. } = variant.scoring {
+            require!(config.max_players == 2, DiceGameError::InvalidVariant);
+        }
         
-        let player2_pubkey = match_data.opponent.ok_or(DiceGameError::NoOpponent)?;
-        let player2_roll = DiceRoll::new(
-            player2_pubkey,
-            game_state[2],
-            game_state[3]
+        // Tous les lancers du match tiennent dans game_state
+        require!(config.rounds >= 1, DiceGameError::TooFewRounds);
+        require!(
+            DiceGameState::max_len(config.max_players, config.rounds, variant.num_dice)
+                <= UniversalMatch::MAX_GAME_STATE_SIZE,
+            DiceGameError::TooManyRounds
         );
-        
-        // Comparer les totaux
-        if player1_roll.total > player2_roll.total {
-            Ok(GameResult::Player1Wins)
-        } else if player2_roll.total > player1_roll.total {
-            Ok(GameResult::Player2Wins)
-        } else {
-            // En cas d'égalité, regarder le plus haut dé unique
-            let p1_max = player1_roll.dice1.max(player1_roll.dice2);
-            let p2_max = player2_roll.dice1.max(player2_roll.dice2);
-            
-            if p1_max > p2_max {
-                Ok(GameResult::Player1Wins)
-            } else if p2_max > p1_max {
-                Ok(GameResult::Player2Wins)
-            } else {
-                Ok(GameResult::Draw)
-            }
-        }
+        Ok(())
+    }
+    
+    fn validate_move(&self, _move_data: &[u8]) -> Result<()> {
+        // Les faces choisies par un joueur ne sont jamais acceptées
+        Err(DiceGameError::PlayerRollsNotAllowed.into())
+    }
+    
+    fn move_size(&self) -> usize {
+        0
+    }
+    
+    fn manages_rounds(&self) -> bool {
+        true
+    }
+    
+    fn max_seats(&self) -> u8 {
+        UniversalMatch::MAX_SEATS as u8
+    }
+    
+    fn initialize_state(&self, match_data: &mut UniversalMatch, _ctx: &GameContext) -> Result<()> {
+        let state = DiceGameState::new(match_data.seats.len(), match_data.game_config.rounds);
+        match_data.game_state = state.try_to_vec()?;
+        Ok(())
+    }
+    
+    fn can_make_move(&self, _player: &Pubkey, _match_data: &UniversalMatch) -> bool {
+        false
+    }
+    
+    fn supports_roll(&self) -> bool {
+        true
+    }
+    
+    fn roll(&self, match_data: &mut UniversalMatch, ctx: &GameContext) -> Result<Vec<Vec<u8>>> {
+        let rolls = Self::roll_next_round(match_data, ctx)?;
+        Ok(rolls.into_iter().map(|roll| roll.dice).collect())
+    }
+    
+    fn is_round_complete(&self, match_data: &UniversalMatch, _ctx: &GameContext) -> bool {
+        Self::load_state(match_data)
+            .is_ok_and(|state| state.current_round == state.max_rounds)
+    }
+    
+    fn determine_winner(&self, match_data: &UniversalMatch, _ctx: &GameContext) -> Result<GameResult> {
+        let variant = DiceVariant::from_config(&match_data.game_config)?;
+        Self::load_state(match_data)?.get_winner(&variant)
+    }
+    
+    fn determine_outcome(&self, match_data: &UniversalMatch, _ctx: &GameContext) -> Result<MatchOutcome> {
+        let variant = DiceVariant::from_config(&match_data.game_config)?;
+        Self::load_state(match_data)?.get_outcome(&variant)
     }
     
     fn encode_move(&self, move_data: &[u8]) -> Vec<u8> {
@@ -118,7 +193,185 @@ impl GameLogic for DiceGame {
             min_bet: 5_000_000,       // 0.005 SOL
             max_bet: 1_000_000_000_000, // 1000 SOL
             rounds: 1,
-            custom_params: [0; 16],
+            custom_params: [0; 16],   // 2d6, somme, départage au plus haut dé
+        }
+    }
+}
+
+// ===========================
+// DICE VARIANTS
+// ===========================
+
+/// Règle de score d'un lancer
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum DiceScoring {
+    /// Somme des dés
+    Sum,
+    /// Plus haut dé
+    HighestSingle,
+    /// Somme des dés, plus un bonus si deux dés sont identiques
+    DoublesBonus { bonus: u8 },
+    /// Lancer de table commun: le créateur gagne si le total tombe de son côté
+    /// de la cible, l'opposant sinon; un total égal à la cible est une égalité
+    OverUnder { target: u8, creator_side: OverUnderSide },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum OverUnderSide {
+    Over = 0,
+    Under = 1,
+}
+
+/// Départage de deux scores égaux
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum DiceTiebreak {
+    /// Comparer les dés du plus haut au plus bas
+    HighestDie,
+    /// Égalité: le round est nul
+    Split,
+}
+
+/// Variante de dés encodée dans `GameConfig::custom_params`:
+/// - [0] nombre de dés (0 = 2)
+/// - [1] faces par dé (0 = 6)
+/// - [2] règle de score (0 = somme, 1 = plus haut dé, 2 = bonus de double, 3 = over/under)
+/// - [3] paramètre de la règle (bonus du double, ou cible de l'over/under)
+/// - [4] départage (0 = plus haut dé, 1 = égalité)
+/// - [5] côté du créateur pour l'over/under (0 = over, 1 = under)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct DiceVariant {
+    pub num_dice: u8,
+    pub faces: u8,
+    pub scoring: DiceScoring,
+    pub tiebreak: DiceTiebreak,
+}
+
+impl DiceVariant {
+    pub const MAX_DICE: u8 = 5;
+    pub const MIN_FACES: u8 = 2;
+    pub const MAX_FACES: u8 = 20;
+    
+    pub const NUM_DICE_PARAM: usize = 0;
+    pub const FACES_PARAM: usize = 1;
+    pub const SCORING_PARAM: usize = 2;
+    pub const SCORING_ARG_PARAM: usize = 3;
+    pub const TIEBREAK_PARAM: usize = 4;
+    pub const SIDE_PARAM: usize = 5;
+    
+    pub fn from_config(config: &GameConfig) -> Result<Self> {
+        let params = &config.custom_params;
+        
+        let num_dice = match params[Self::NUM_DICE_PARAM] {
+            0 => 2,
+            n => n,
+        };
+        let faces = match params[Self::FACES_PARAM] {
+            0 => 6,
+            n => n,
+        };
+        require!(num_dice <= Self::MAX_DICE, DiceGameError::InvalidVariant);
+        require!(
+            (Self::MIN_FACES..=Self::MAX_FACES).contains(&faces),
+            DiceGameError::InvalidVariant
+        );
+        
+        let max_total = num_dice * faces;
+        let scoring = match params[Self::SCORING_PARAM] {
+            0 => DiceScoring::Sum,
+            1 => DiceScoring::HighestSingle,
+            2 => {
+                require!(num_dice >= 2, DiceGameError::InvalidVariant);
+                let bonus = params[Self::SCORING_ARG_PARAM];
+                require!(bonus > 0, DiceGameError::InvalidVariant);
+                DiceScoring::DoublesBonus { bonus }
+            },
+            3 => {
+                let target = params[Self::SCORING_ARG_PARAM];
+                require!(
+                    target > num_dice && target < max_total,
+                    DiceGameError::InvalidVariant
+                );
+                let creator_side = match params[Self::SIDE_PARAM] {
+                    0 => OverUnderSide::Over,
+                    1 => OverUnderSide::Under,
+                    _ => return Err(DiceGameError::InvalidVariant.into()),
+                };
+                DiceScoring::OverUnder { target, creator_side }
+            },
+            _ => return Err(DiceGameError::InvalidVariant.into()),
+        };
+        
+        let tiebreak = match params[Self::TIEBREAK_PARAM] {
+            0 => DiceTiebreak::HighestDie,
+            1 => DiceTiebreak::Split,
+            _ => return Err(DiceGameError::InvalidVariant.into()),
+        };
+        
+        Ok(DiceVariant {
+            num_dice,
+            faces,
+            scoring,
+            tiebreak,
+        })
+    }
+    
+    /// Score d'un lancer selon la règle (l'over/under se juge sur le total)
+    pub fn score(&self, roll: &DiceRoll) -> u16 {
+        match self.scoring {
+            DiceScoring::Sum | DiceScoring::OverUnder { .. } => roll.total as u16,
+            DiceScoring::HighestSingle => roll.highest() as u16,
+            DiceScoring::DoublesBonus { bonus } => {
+                let bonus = if roll.has_double() { bonus as u16 } else { 0 };
+                roll.total as u16 + bonus
+            },
+        }
+    }
+    
+    /// Résultat d'un round à partir des lancers des deux joueurs
+    pub fn compare(&self, player1_roll: &DiceRoll, player2_roll: &DiceRoll) -> GameResult {
+        if let DiceScoring::OverUnder { target, creator_side } = self.scoring {
+            let total = player1_roll.total;
+            if total == target {
+                return GameResult::Draw;
+            }
+            let over = total > target;
+            return if over == (creator_side == OverUnderSide::Over) {
+                GameResult::Player1Wins
+            } else {
+                GameResult::Player2Wins
+            };
+        }
+        
+        match self.ordering(player1_roll, player2_roll) {
+            std::cmp::Ordering::Greater => GameResult::Player1Wins,
+            std::cmp::Ordering::Less => GameResult::Player2Wins,
+            std::cmp::Ordering::Equal => GameResult::Draw,
+        }
+    }
+    
+    /// Compare deux lancers: score d'abord, puis départage
+    pub fn ordering(&self, roll: &DiceRoll, other: &DiceRoll) -> std::cmp::Ordering {
+        self.score(roll)
+            .cmp(&self.score(other))
+            .then_with(|| match self.tiebreak {
+                DiceTiebreak::HighestDie => roll.sorted_desc().cmp(&other.sorted_desc()),
+                DiceTiebreak::Split => std::cmp::Ordering::Equal,
+            })
+    }
+    
+    /// Siège qui remporte un round de plusieurs lancers (None si égalité en tête)
+    pub fn round_winner(&self, rolls: &[&DiceRoll]) -> Option<u8> {
+        let best = rolls
+            .iter()
+            .copied()
+            .max_by(|roll, other| self.ordering(roll, other))?;
+        let mut leaders = rolls
+            .iter()
+            .enumerate()
+            .filter(|(_, roll)| self.ordering(roll, best).is_eq());
+        match (leaders.next(), leaders.next()) {
+            (Some((seat, _)), None) => Some(seat as u8),
+            _ => None,
         }
     }
 }
@@ -127,62 +380,64 @@ impl GameLogic for DiceGame {
 // DICE GAME STATE
 // ===========================
 
-/// État du jeu de dés avec support pour plusieurs rounds
+/// État du jeu de dés avec support pour plusieurs rounds et sièges
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct DiceGameState {
-    pub player1_rolls: Vec<DiceRoll>,
-    pub player2_rolls: Vec<DiceRoll>,
+    pub rolls: Vec<Vec<DiceRoll>>, // Lancers de chaque siège, round par round
     pub current_round: u8,
     pub max_rounds: u8,
-    pub seed: [u8; 32], // Seed pour la génération aléatoire
 }
 
 impl DiceGameState {
-    pub fn new(max_rounds: u8, seed: [u8; 32]) -> Self {
+    /// Taille sérialisée une fois tous les rounds lancés
+    pub fn max_len(seats: u8, max_rounds: u8, num_dice: u8) -> usize {
+        let roll_len = 4 + num_dice as usize + 1;
+        4 + seats as usize * (4 + max_rounds as usize * roll_len) + 1 + 1
+    }
+    
+    pub fn new(seats: usize, max_rounds: u8) -> Self {
         DiceGameState {
-            player1_rolls: Vec::new(),
-            player2_rolls: Vec::new(),
+            rolls: vec![Vec::new(); seats],
             current_round: 0,
             max_rounds,
-            seed,
         }
     }
     
-    pub fn add_roll(&mut self, player: Pubkey, dice1: u8, dice2: u8) -> Result<()> {
-        let roll = DiceRoll::new(player, dice1, dice2);
-        
-        if self.player1_rolls.len() < self.max_rounds as usize {
-            self.player1_rolls.push(roll);
-        } else if self.player2_rolls.len() < self.max_rounds as usize {
-            self.player2_rolls.push(roll);
-        } else {
-            return Err(DiceGameError::MaxRoundsReached.into());
-        }
+    /// Enregistre les lancers d'un round, un par siège
+    pub fn add_round(&mut self, rolls: Vec<DiceRoll>) -> Result<()> {
+        require!(rolls.len() == self.rolls.len(), DiceGameError::InvalidData);
+        require!(
+            self.current_round < self.max_rounds,
+            DiceGameError::MaxRoundsReached
+        );
         
-        if self.player1_rolls.len() == self.player2_rolls.len() {
-            self.current_round += 1;
+        for (seat_rolls, roll) in self.rolls.iter_mut().zip(rolls) {
+            seat_rolls.push(roll);
         }
+        self.current_round += 1;
         
         Ok(())
     }
     
-    pub fn get_winner(&self) -> Result<GameResult> {
+    /// Vainqueur d'un duel créateur/opposant
+    pub fn get_winner(&self, variant: &DiceVariant) -> Result<GameResult> {
         require!(
             self.current_round == self.max_rounds,
             DiceGameError::GameNotComplete
         );
+        require!(self.rolls.len() == 2, DiceGameError::InvalidData);
         
         let mut player1_wins = 0;
         let mut player2_wins = 0;
         
         for i in 0..self.max_rounds as usize {
-            let p1_roll = &self.player1_rolls[i];
-            let p2_roll = &self.player2_rolls[i];
+            let p1_roll = &self.rolls[UniversalMatch::CREATOR_SEAT as usize][i];
+            let p2_roll = &self.rolls[UniversalMatch::OPPONENT_SEAT as usize][i];
             
-            if p1_roll.total > p2_roll.total {
-                player1_wins += 1;
-            } else if p2_roll.total > p1_roll.total {
-                player2_wins += 1;
+            match variant.compare(p1_roll, p2_roll) {
+                GameResult::Player1Wins => player1_wins += 1,
+                GameResult::Player2Wins => player2_wins += 1,
+                _ => {},
             }
         }
         
@@ -194,34 +449,50 @@ impl DiceGameState {
             Ok(GameResult::Draw)
         }
     }
+    
+    /// Résultat du match pour n'importe quel nombre de sièges: classement au
+    /// nombre de rounds remportés
+    pub fn get_outcome(&self, variant: &DiceVariant) -> Result<MatchOutcome> {
+        if self.rolls.len() == 2 {
+            return Ok(MatchOutcome::from_result(self.get_winner(variant)?));
+        }
+        require!(
+            self.current_round == self.max_rounds,
+            DiceGameError::GameNotComplete
+        );
+        
+        let mut round_wins = vec![0u16; self.rolls.len()];
+        for i in 0..self.max_rounds as usize {
+            let round_rolls: Vec<&DiceRoll> = self.rolls.iter().map(|rolls| &rolls[i]).collect();
+            if let Some(seat) = variant.round_winner(&round_rolls) {
+                round_wins[seat as usize] += 1;
+            }
+        }
+        
+        Ok(MatchOutcome::from_scores(&round_wins))
+    }
 }
 
 // ===========================
 // RANDOM NUMBER GENERATION
 // ===========================
 
-use sha2::{Digest, Sha256};
-
-/// Génère des nombres aléatoires pour les dés en utilisant la blockchain
+/// Génère les dés d'un lanceur (joueur, ou table pour l'over/under)
+/// depuis l'aléatoire commun du match
 pub fn generate_dice_rolls(
-    slot: u64,
-    match_id: &Pubkey,
-    player: &Pubkey,
+    randomness: &dyn RandomnessSource,
+    variant: &DiceVariant,
+    roller: &Pubkey,
     round: u8,
-) -> (u8, u8) {
-    let mut hasher = Sha256::new();
-    hasher.update(slot.to_le_bytes());
-    hasher.update(match_id.as_ref());
-    hasher.update(player.as_ref());
-    hasher.update([round]);
+) -> Result<Vec<u8>> {
+    let domain = [b"dice".as_slice(), roller.as_ref(), &[round]].concat();
     
-    let hash = hasher.finalize();
-    
-    // Utiliser les deux premiers bytes du hash pour les dés
-    let dice1 = (hash[0] % 6) + 1;
-    let dice2 = (hash[1] % 6) + 1;
-    
-    (dice1, dice2)
+    (1..=variant.num_dice)
+        .map(|die| {
+            let value = randomness.random_below(&[domain.as_slice(), &[die]].concat(), variant.faces as u64)?;
+            Ok(value as u8 + 1)
+        })
+        .collect()
 }
 
 // ===========================
@@ -230,7 +501,7 @@ pub fn generate_dice_rolls(
 
 #[error_code]
 pub enum DiceGameError {
-    #[msg("Invalid dice value (must be 1-6)")]
+    #[msg("Invalid dice value")]
     InvalidDiceValue,
     
     #[msg("Invalid move data")]
@@ -253,4 +524,147 @@ pub enum DiceGameError {
     
     #[msg("Invalid seed")]
     InvalidSeed,
-}
\ No newline at end of file
+    
+    #[msg("Dice are rolled on-chain with roll_dice, player rolls are not accepted")]
+    PlayerRollsNotAllowed,
+    
+    #[msg("Too many rounds for a dice match")]
+    TooManyRounds,
+    
+    #[msg("A dice match needs at least one round")]
+    TooFewRounds,
+    
+    #[msg("Invalid dice variant in custom_params")]
+    InvalidVariant,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn config(params: &[u8]) -> GameConfig {
+        let mut config = GameConfig {
+            max_players: 2,
+            rounds: 1,
+            ..GameConfig::default()
+        };
+        config.custom_params[..params.len()].copy_from_slice(params);
+        config
+    }
+    
+    fn roll(dice: &[u8]) -> DiceRoll {
+        DiceRoll::new(dice.to_vec())
+    }
+    
+    #[test]
+    fn parses_three_d6_highest_total() {
+        // 3 dés, 6 faces par défaut, somme, départage au plus haut dé
+        let variant = DiceVariant::from_config(&config(&[3, 0, 0, 0, 0])).unwrap();
+        
+        assert_eq!(variant.num_dice, 3);
+        assert_eq!(variant.faces, 6);
+        assert_eq!(variant.scoring, DiceScoring::Sum);
+        assert_eq!(variant.tiebreak, DiceTiebreak::HighestDie);
+        assert_eq!(variant.score(&roll(&[6, 5, 4])), 15);
+    }
+    
+    #[test]
+    fn parses_over_under_seven() {
+        let variant = DiceVariant::from_config(&config(&[0, 0, 3, 7, 0, 1])).unwrap();
+        
+        assert_eq!(variant.num_dice, 2);
+        assert_eq!(
+            variant.scoring,
+            DiceScoring::OverUnder { target: 7, creator_side: OverUnderSide::Under }
+        );
+        
+        // La cible doit être atteignable des deux côtés
+        assert!(DiceVariant::from_config(&config(&[0, 0, 3, 2])).is_err());
+        assert!(DiceVariant::from_config(&config(&[0, 0, 3, 12])).is_err());
+        assert!(DiceVariant::from_config(&config(&[0, 0, 3, 7, 0, 2])).is_err());
+    }
+    
+    #[test]
+    fn over_under_total_on_target_is_a_draw() {
+        let variant = DiceVariant::from_config(&config(&[0, 0, 3, 7, 0, 0])).unwrap();
+        let on_target = roll(&[3, 4]);
+        
+        assert_eq!(variant.compare(&on_target, &on_target), GameResult::Draw);
+        assert_eq!(variant.compare(&roll(&[4, 4]), &roll(&[4, 4])), GameResult::Player1Wins);
+        assert_eq!(variant.compare(&roll(&[1, 2]), &roll(&[1, 2])), GameResult::Player2Wins);
+    }
+    
+    #[test]
+    fn doubles_bonus_beats_a_higher_plain_total() {
+        let variant = DiceVariant::from_config(&config(&[0, 0, 2, 5])).unwrap();
+        
+        assert_eq!(variant.scoring, DiceScoring::DoublesBonus { bonus: 5 });
+        assert_eq!(variant.score(&roll(&[3, 3])), 11);
+        assert_eq!(variant.score(&roll(&[6, 4])), 10);
+        assert_eq!(variant.compare(&roll(&[3, 3]), &roll(&[6, 4])), GameResult::Player1Wins);
+        
+        // Un seul dé ne peut pas faire de double
+        assert!(DiceVariant::from_config(&config(&[1, 0, 2, 5])).is_err());
+    }
+    
+    #[test]
+    fn highest_die_breaks_equal_totals() {
+        let highest_die = DiceVariant::from_config(&config(&[0, 0, 0, 0, 0])).unwrap();
+        let split = DiceVariant::from_config(&config(&[0, 0, 0, 0, 1])).unwrap();
+        
+        assert_eq!(highest_die.compare(&roll(&[6, 1]), &roll(&[4, 3])), GameResult::Player1Wins);
+        assert_eq!(highest_die.compare(&roll(&[3, 4]), &roll(&[4, 3])), GameResult::Draw);
+        assert_eq!(split.compare(&roll(&[6, 1]), &roll(&[4, 3])), GameResult::Draw);
+        
+        // Table à trois: le départage désigne un seul vainqueur
+        assert_eq!(
+            highest_die.round_winner(&[&roll(&[4, 3]), &roll(&[6, 1]), &roll(&[2, 2])]),
+            Some(1)
+        );
+        assert_eq!(split.round_winner(&[&roll(&[4, 3]), &roll(&[6, 1]), &roll(&[2, 2])]), None);
+    }
+    
+    #[test]
+    fn rounds_must_be_at_least_one_and_fit_in_game_state() {
+        // Bornes permissives: seule la validation propre au jeu est testée
+        let bounds = GameConfigBounds {
+            min_players: 2,
+            max_players: 8,
+            min_rounds: 0,
+            max_rounds: u8::MAX,
+            min_bet: 0,
+            max_bet: u64::MAX,
+            max_custom_params: [u8::MAX; 16],
+        };
+        let mut no_rounds = config(&[]);
+        no_rounds.rounds = 0;
+        let mut too_many_rounds = config(&[]);
+        too_many_rounds.rounds = 100;
+        
+        assert!(DiceGame.validate_config(&config(&[]), &bounds).is_ok());
+        assert_eq!(
+            DiceGame.validate_config(&no_rounds, &bounds).unwrap_err(),
+            DiceGameError::TooFewRounds.into()
+        );
+        assert_eq!(
+            DiceGame.validate_config(&too_many_rounds, &bounds).unwrap_err(),
+            DiceGameError::TooManyRounds.into()
+        );
+    }
+    
+    #[test]
+    fn max_len_matches_the_serialized_state() {
+        for (seats, rounds, num_dice) in [(2u8, 1u8, 2u8), (3, 4, 3), (8, 2, 5)] {
+            let mut state = DiceGameState::new(seats as usize, rounds);
+            for _ in 0..rounds {
+                let rolls = vec![roll(&vec![6; num_dice as usize]); seats as usize];
+                state.add_round(rolls).unwrap();
+            }
+            
+            assert_eq!(
+                state.try_to_vec().unwrap().len(),
+                DiceGameState::max_len(seats, rounds, num_dice)
+            );
+        }
+    }
+}
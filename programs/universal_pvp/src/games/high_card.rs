@@ -0,0 +1,196 @@
+use anchor_lang::prelude::*;
+use crate::universal_engine::{GameResult, GameConfig, MatchOutcome, RandomnessSource, UniversalMatch};
+use super::{GameContext, GameLogic};
+
+// ===========================
+// HIGH CARD MODULE
+// ===========================
+
+/// Carte haute: chaque joueur reçoit une carte d'un jeu de 52 cartes mélangé
+/// avec l'aléatoire commun du match. Les joueurs ne choisissent rien.
+/// La plus haute carte de la table l'emporte, quel que soit le nombre de sièges.
+pub struct HighCardGame;
+
+impl HighCardGame {
+    /// Nombre de cartes dans le paquet
+    pub const DECK_SIZE: u8 = 52;
+    
+    /// Mélange le paquet du round (Fisher-Yates sur l'aléatoire commun)
+    pub fn shuffle_deck(randomness: &dyn RandomnessSource, round: u8) -> Result<[u8; 52]> {
+        let mut deck = [0u8; 52];
+        for (i, card) in deck.iter_mut().enumerate() {
+            *card = i as u8;
+        }
+        
+        for i in (1..deck.len()).rev() {
+            let domain = [b"high_card".as_slice(), &[round], &[i as u8]].concat();
+            let j = randomness.random_below(&domain, i as u64 + 1)? as usize;
+            deck.swap(i, j);
+        }
+        
+        Ok(deck)
+    }
+    
+    /// Distribue une carte à chaque siège, dans l'ordre des sièges
+    pub fn deal(randomness: &dyn RandomnessSource, round: u8, seats: usize) -> Result<Vec<Card>> {
+        let deck = Self::shuffle_deck(randomness, round)?;
+        require!(seats <= deck.len(), HighCardError::InvalidCard);
+        deck[..seats].iter().map(|index| Card::from_index(*index)).collect()
+    }
+    
+    /// Siège ayant reçu la plus haute carte
+    pub fn best_seat(cards: &[Card]) -> Result<u8> {
+        cards
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, card)| (card.rank, card.suit))
+            .map(|(seat, _)| seat as u8)
+            .ok_or(error!(HighCardError::InvalidCard))
+    }
+}
+
+impl GameLogic for HighCardGame {
+    fn name(&self) -> &'static str {
+        "High Card"
+    }
+    
+    fn validate_move(&self, _move_data: &[u8]) -> Result<()> {
+        // Les cartes sont distribuées par le programme, jamais choisies
+        Err(HighCardError::NoMovesAllowed.into())
+    }
+    
+    fn move_size(&self) -> usize {
+        0
+    }
+    
+    fn max_seats(&self) -> u8 {
+        UniversalMatch::MAX_SEATS as u8
+    }
+    
+    fn initialize_state(&self, match_data: &mut UniversalMatch, _ctx: &GameContext) -> Result<()> {
+        // Rien à stocker: les cartes se déduisent de l'aléatoire et du round
+        match_data.game_state = Vec::new();
+        Ok(())
+    }
+    
+    fn can_make_move(&self, _player: &Pubkey, _match_data: &UniversalMatch) -> bool {
+        false
+    }
+    
+    fn is_round_complete(&self, match_data: &UniversalMatch, ctx: &GameContext) -> bool {
+        ctx.randomness(match_data).is_ok()
+    }
+    
+    fn determine_winner(&self, match_data: &UniversalMatch, ctx: &GameContext) -> Result<GameResult> {
+        let randomness = ctx.randomness(match_data)?;
+        let cards = Self::deal(&randomness, match_data.current_round, 2)?;
+        
+        msg!("🃏 Cards dealt: {:?} vs {:?}", cards[0], cards[1]);
+        
+        // Deux cartes d'un même paquet sont toujours différentes: pas d'égalité
+        if cards[0].beats(&cards[1]) {
+            Ok(GameResult::Player1Wins)
+        } else {
+            Ok(GameResult::Player2Wins)
+        }
+    }
+    
+    fn determine_outcome(&self, match_data: &UniversalMatch, ctx: &GameContext) -> Result<MatchOutcome> {
+        let randomness = ctx.randomness(match_data)?;
+        let cards = Self::deal(&randomness, match_data.current_round, match_data.seats.len())?;
+        
+        msg!("🃏 Cards dealt: {:?}", cards);
+        
+        Ok(MatchOutcome::Winner(Self::best_seat(&cards)?))
+    }
+    
+    fn round_data(&self, match_data: &UniversalMatch, ctx: &GameContext) -> Result<Vec<u8>> {
+        let randomness = ctx.randomness(match_data)?;
+        let cards = Self::deal(&randomness, match_data.current_round, match_data.seats.len())?;
+        Ok(cards.iter().map(Card::to_index).collect())
+    }
+    
+    fn encode_move(&self, move_data: &[u8]) -> Vec<u8> {
+        move_data.to_vec()
+    }
+    
+    fn decode_move(&self, encoded: &[u8]) -> Result<Vec<u8>> {
+        Ok(encoded.to_vec())
+    }
+    
+    fn default_config(&self) -> GameConfig {
+        GameConfig {
+            max_players: 2,
+            min_bet: 5_000_000,         // 0.005 SOL
+            max_bet: 100_000_000_000,   // 100 SOL
+            rounds: 1,
+            custom_params: [0; 16],
+        }
+    }
+}
+
+// ===========================
+// CARDS
+// ===========================
+
+/// Couleurs, dans l'ordre du bridge: trèfle < carreau < cœur < pique
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Suit {
+    Clubs = 0,
+    Diamonds = 1,
+    Hearts = 2,
+    Spades = 3,
+}
+
+impl Suit {
+    pub fn from_u8(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(Suit::Clubs),
+            1 => Ok(Suit::Diamonds),
+            2 => Ok(Suit::Hearts),
+            3 => Ok(Suit::Spades),
+            _ => Err(error!(HighCardError::InvalidCard)),
+        }
+    }
+}
+
+/// Une carte: valeur de 2 à 14 (as = 14) et couleur
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct Card {
+    pub rank: u8,
+    pub suit: Suit,
+}
+
+impl Card {
+    /// Index 0..52 ordonné par valeur puis couleur (0 = 2♣, 51 = A♠)
+    pub fn from_index(index: u8) -> Result<Self> {
+        require!(index < HighCardGame::DECK_SIZE, HighCardError::InvalidCard);
+        
+        Ok(Card {
+            rank: index / 4 + 2,
+            suit: Suit::from_u8(index % 4)?,
+        })
+    }
+    
+    pub fn to_index(&self) -> u8 {
+        (self.rank - 2) * 4 + self.suit as u8
+    }
+    
+    /// Compare d'abord la valeur, puis la couleur
+    pub fn beats(&self, other: &Card) -> bool {
+        (self.rank, self.suit) > (other.rank, other.suit)
+    }
+}
+
+// ===========================
+// HIGH CARD ERRORS
+// ===========================
+
+#[error_code]
+pub enum HighCardError {
+    #[msg("Cards are dealt by the program and cannot be chosen")]
+    NoMovesAllowed,
+    
+    #[msg("Invalid card index (must be 0-51)")]
+    InvalidCard,
+}
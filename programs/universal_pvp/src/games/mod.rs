@@ -1,21 +1,124 @@
 pub mod rock_paper_scissors;
 pub mod dice;
+pub mod coin_flip;
+pub mod high_card;
+pub mod custom;
 pub mod round_manager;
 
-// Futurs modules de jeux
-// pub mod coin_flip;
-// pub mod high_card;
-
 use anchor_lang::prelude::*;
-use crate::universal_engine::{GameType, GameResult, UniversalMatch};
+use crate::universal_engine::{
+    GameConfig, GameConfigBounds, GameResult, GameType, MatchOutcome, MatchRandomness, Payout, RandomnessRequest, UniversalGameError, UniversalMatch,
+};
 
 /// Trait que tous les jeux doivent implémenter
 pub trait GameLogic {
+    /// Nom affiché du jeu
+    fn name(&self) -> &'static str;
+    
+    /// Valide la configuration demandée à la création du match contre les
+    /// bornes du registre. Un jeu qui surcharge doit appeler `bounds.check`
+    fn validate_config(&self, config: &GameConfig, bounds: &GameConfigBounds) -> Result<()> {
+        bounds.check(config)
+    }
+    
     /// Valide un mouvement pour ce jeu
     fn validate_move(&self, move_data: &[u8]) -> Result<()>;
     
+    /// Taille en octets du slot de mouvement de chaque siège dans game_state
+    fn move_size(&self) -> usize;
+    
+    /// Les mouvements passent par commit_move/reveal_move au lieu de submit_move
+    fn supports_commit_reveal(&self) -> bool {
+        false
+    }
+    
+    /// Le jeu gère lui-même ses rounds dans game_state (pas de RoundManager)
+    fn manages_rounds(&self) -> bool {
+        false
+    }
+    
+    /// Nombre max de sièges que le jeu sait arbitrer
+    fn max_seats(&self) -> u8 {
+        2
+    }
+    
+    /// Initialise game_state quand la dernière place du match est prise
+    fn initialize_state(&self, match_data: &mut UniversalMatch, ctx: &GameContext) -> Result<()>;
+    
+    /// Le joueur peut-il jouer maintenant? (siège du match, pas encore joué ce round)
+    fn can_make_move(&self, player: &Pubkey, match_data: &UniversalMatch) -> bool {
+        match_data
+            .seat_of(player)
+            .is_some_and(|seat| !match_data.has_submitted(seat))
+    }
+    
+    /// Applique le mouvement du joueur: par défaut, l'écrit dans le slot de son siège
+    fn process_move(
+        &self,
+        player: &Pubkey,
+        move_data: &[u8],
+        match_data: &mut UniversalMatch,
+        _ctx: &GameContext,
+    ) -> Result<()> {
+        let seat = match_data
+            .seat_of(player)
+            .ok_or(UniversalGameError::Unauthorized)?;
+        self.validate_move(move_data)?;
+        match_data.record_move(seat, move_data, self.move_size())
+    }
+    
+    /// Le round en cours peut-il être réglé?
+    fn is_round_complete(&self, match_data: &UniversalMatch, ctx: &GameContext) -> bool;
+    
     /// Détermine le gagnant basé sur les mouvements
-    fn determine_winner(&self, match_data: &UniversalMatch) -> Result<GameResult>;
+    fn determine_winner(&self, match_data: &UniversalMatch, ctx: &GameContext) -> Result<GameResult>;
+    
+    /// Résultat final du match; par défaut celui d'un duel créateur/opposant.
+    /// Les jeux à plus de deux sièges le surchargent
+    fn determine_outcome(&self, match_data: &UniversalMatch, ctx: &GameContext) -> Result<MatchOutcome> {
+        Ok(MatchOutcome::from_result(self.determine_winner(match_data, ctx)?))
+    }
+    
+    /// Répartition du pot pour ce résultat, enregistrée sur le match et payée
+    /// par claim_winnings. Un jeu peut surcharger pour des gains partiels,
+    /// un podium ou des side pots, tant que le total reste égal au pot
+    fn calculate_payouts(&self, match_data: &UniversalMatch, outcome: &MatchOutcome) -> Result<Vec<Payout>> {
+        match_data.outcome_payouts(outcome)
+    }
+    
+    /// Données du round conservées dans l'historique du RoundManager
+    fn round_data(&self, match_data: &UniversalMatch, _ctx: &GameContext) -> Result<Vec<u8>> {
+        Ok(match_data.game_state.clone())
+    }
+    
+    /// Réinitialise game_state pour le round suivant (ou le rejeu d'une égalité)
+    fn reset_round(&self, match_data: &mut UniversalMatch, ctx: &GameContext) -> Result<()> {
+        self.initialize_state(match_data, ctx)
+    }
+    
+    /// Siège qui gagne par forfait (claim_timeout_forfeit) parce que
+    /// l'adversaire n'a pas joué à temps. Par défaut, pas de forfait
+    fn forfeit_winner(&self, _match_data: &UniversalMatch, _ctx: &GameContext) -> Option<u8> {
+        None
+    }
+    
+    /// Le match a-t-il progressé (round enregistré, siège qui a joué, seeds
+    /// engagées)? Un match qui a progressé ne s'annule plus avec remboursement
+    fn has_progress(&self, match_data: &UniversalMatch) -> bool {
+        match_data.has_round_history() ||
+            match_data.seats.iter().any(|seat| seat.has_moved) ||
+            match_data.seeds_committed()
+    }
+    
+    /// Les dés du jeu se lancent via roll_dice
+    fn supports_roll(&self) -> bool {
+        false
+    }
+    
+    /// Lance les dés du round pour chaque siège (roll_dice)
+    fn roll(&self, _match_data: &mut UniversalMatch, _ctx: &GameContext) -> Result<Vec<Vec<u8>>> {
+        err!(UniversalGameError::InvalidGameType)
+    }
     
     /// Encode un mouvement en bytes
     fn encode_move(&self, move_data: &[u8]) -> Vec<u8>;
@@ -24,5 +127,53 @@ pub trait GameLogic {
     fn decode_move(&self, encoded: &[u8]) -> Result<Vec<u8>>;
     
     /// Retourne la configuration par défaut du jeu
-    fn default_config(&self) -> crate::universal_engine::GameConfig;
-}
\ No newline at end of file
+    fn default_config(&self) -> GameConfig;
+}
+
+/// Mouvement d'un jeu à commit-reveal, tel que passé à `process_move`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub enum CommitRevealMove {
+    /// sha256(move_data || salt)
+    Commit([u8; 32]),
+    Reveal { move_data: Vec<u8>, salt: [u8; 32] },
+}
+
+/// Contexte d'exécution passé aux jeux par les instructions
+pub struct GameContext<'a> {
+    pub now: i64,
+    pub randomness_request: Option<&'a RandomnessRequest>,
+}
+
+impl<'a> GameContext<'a> {
+    pub fn new(now: i64, randomness_request: Option<&'a RandomnessRequest>) -> Self {
+        GameContext {
+            now,
+            randomness_request,
+        }
+    }
+    
+    /// Aléatoire commun du match (seeds révélées + VRF éventuel)
+    pub fn randomness(&self, match_data: &UniversalMatch) -> Result<MatchRandomness> {
+        MatchRandomness::from_match(match_data, self.randomness_request)
+    }
+}
+
+// ===========================
+// GAME ROUTER
+// ===========================
+
+/// Associe chaque GameType à son implémentation de GameLogic.
+/// Ajouter un jeu = un module + une ligne ici, sans toucher aux instructions.
+pub struct GameRouter;
+
+impl GameRouter {
+    pub fn get_handler(game_type: GameType) -> Result<Box<dyn GameLogic>> {
+        match game_type {
+            GameType::RockPaperScissors => Ok(Box::new(rock_paper_scissors::RockPaperScissors)),
+            GameType::Dice => Ok(Box::new(dice::DiceGame)),
+            GameType::CoinFlip => Ok(Box::new(coin_flip::CoinFlipGame)),
+            GameType::HighCard => Ok(Box::new(high_card::HighCardGame)),
+            GameType::Custom(id) => Ok(Box::new(custom::CustomGame { id })),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::universal_engine::{GameType, GameResult, GameConfig, UniversalMatch};
-use super::GameLogic;
+use crate::universal_engine::{GameResult, GameConfig, SeatEntropy, UniversalGameError, UniversalMatch};
+use super::{CommitRevealMove, GameContext, GameLogic};
 
 // ===========================
 // ROCK PAPER SCISSORS MODULE
@@ -36,27 +36,123 @@ impl RPSChoice {
 pub struct RockPaperScissors;
 
 impl GameLogic for RockPaperScissors {
+    fn name(&self) -> &'static str {
+        "Rock Paper Scissors"
+    }
+    
     fn validate_move(&self, move_data: &[u8]) -> Result<()> {
-        require!(move_data.len() >= 1, RPSGameError::InvalidMoveData);
+        require!(!move_data.is_empty(), RPSGameError::InvalidMoveData);
         let choice_value = move_data[0];
         require!(choice_value <= 2, RPSGameError::InvalidChoice);
         Ok(())
     }
     
-    fn determine_winner(&self, match_data: &UniversalMatch) -> Result<GameResult> {
-        // Extraire les choix depuis game_state
-        let game_state = &match_data.game_state;
-        require!(game_state.len() >= 2, RPSGameError::IncompleteGameState);
+    fn move_size(&self) -> usize {
+        1 // [choix]
+    }
+    
+    fn supports_commit_reveal(&self) -> bool {
+        true
+    }
+    
+    fn initialize_state(&self, match_data: &mut UniversalMatch, ctx: &GameContext) -> Result<()> {
+        // Initialiser l'état RPS avec commit-reveal: la fenêtre de reveal
+        // ne s'ouvrira qu'une fois les deux engagements posés
+        let rps_state = RPSGameState::new(ctx.now + RPSGameState::COMMIT_WINDOW);
+        match_data.game_state = rps_state.to_bytes();
+        Ok(())
+    }
+    
+    fn can_make_move(&self, player: &Pubkey, match_data: &UniversalMatch) -> bool {
+        let Some(seat) = match_data.seat_of(player) else {
+            return false;
+        };
+        let Ok(state) = RPSGameState::from_bytes(&match_data.game_state) else {
+            return false;
+        };
+        
+        // Phase commit: pas encore commis. Phase reveal: tout le monde a commis, pas encore révélé
+        !state.has_committed(seat) ||
+            (state.both_committed() && state.revealed(seat).is_none())
+    }
+    
+    fn process_move(
+        &self,
+        player: &Pubkey,
+        move_data: &[u8],
+        match_data: &mut UniversalMatch,
+        ctx: &GameContext,
+    ) -> Result<()> {
+        let seat = match_data
+            .seat_of(player)
+            .ok_or(UniversalGameError::Unauthorized)?;
+        let rps_move = CommitRevealMove::try_from_slice(move_data)
+            .map_err(|_| error!(RPSGameError::InvalidMoveData))?;
         
-        let player1_choice = RPSChoice::from_u8(game_state[0])?;
-        let player2_choice = RPSChoice::from_u8(game_state[1])?;
+        let mut state = RPSGameState::from_bytes(&match_data.game_state)?;
         
-        if player1_choice == player2_choice {
-            Ok(GameResult::Draw)
-        } else if player1_choice.beats(&player2_choice) {
-            Ok(GameResult::Player1Wins)
-        } else {
-            Ok(GameResult::Player2Wins)
+        match rps_move {
+            CommitRevealMove::Commit(commitment) => {
+                state.commit(seat, commitment, ctx.now)?;
+                match_data.mark_move_submitted(seat)?;
+            },
+            CommitRevealMove::Reveal { move_data, salt } => {
+                self.validate_move(&move_data)?;
+                let choice = RPSChoice::from_u8(move_data[0])?;
+                state.reveal(seat, choice, &salt, ctx.now)?;
+                
+                // Le salt révélé sert aussi d'entropie commit-reveal pour le match
+                match_data.seats[seat as usize].entropy = SeatEntropy {
+                    commitment: *state.commitment(seat),
+                    seed: Some(salt),
+                };
+            },
+        }
+        
+        match_data.game_state = state.to_bytes();
+        Ok(())
+    }
+    
+    fn is_round_complete(&self, match_data: &UniversalMatch, ctx: &GameContext) -> bool {
+        // Les deux reveals, ou la phase en cours expirée (le joueur muet perd le round)
+        RPSGameState::from_bytes(&match_data.game_state)
+            .map(|state| state.is_settleable(ctx.now))
+            .unwrap_or(false)
+    }
+    
+    fn forfeit_winner(&self, match_data: &UniversalMatch, ctx: &GameContext) -> Option<u8> {
+        RPSGameState::from_bytes(&match_data.game_state)
+            .ok()?
+            .forfeit_winner(ctx.now)
+    }
+    
+    fn round_data(&self, match_data: &UniversalMatch, _ctx: &GameContext) -> Result<Vec<u8>> {
+        Ok(RPSGameState::from_bytes(&match_data.game_state)?.round_data())
+    }
+    
+    fn determine_winner(&self, match_data: &UniversalMatch, _ctx: &GameContext) -> Result<GameResult> {
+        // Les choix viennent des reveals vérifiés, jamais de bytes bruts
+        let state = RPSGameState::from_bytes(&match_data.game_state)?;
+        
+        match (state.player1_revealed, state.player2_revealed) {
+            (Some(player1_choice), Some(player2_choice)) => {
+                if player1_choice == player2_choice {
+                    Ok(GameResult::Draw)
+                } else if player1_choice.beats(&player2_choice) {
+                    Ok(GameResult::Player1Wins)
+                } else {
+                    Ok(GameResult::Player2Wins)
+                }
+            },
+            // Deadline dépassée: seul le joueur qui a rempli sa part l'emporte
+            _ => match (
+                state.fulfilled(UniversalMatch::CREATOR_SEAT),
+                state.fulfilled(UniversalMatch::OPPONENT_SEAT),
+            ) {
+                (true, false) => Ok(GameResult::Player1Wins),
+                (false, true) => Ok(GameResult::Player2Wins),
+                _ => Ok(GameResult::Draw),
+            },
         }
     }
     
@@ -90,10 +186,148 @@ pub struct RPSGameState {
     pub player2_commitment: [u8; 32],
     pub player1_revealed: Option<RPSChoice>,
     pub player2_revealed: Option<RPSChoice>,
-    pub reveal_deadline: i64,
+    pub commit_deadline: i64,
+    /// Ouverte seulement quand les deux joueurs ont commis
+    pub reveal_deadline: Option<i64>,
 }
 
 impl RPSGameState {
+    /// Fenêtre de commit après le début d'un round (5 minutes)
+    pub const COMMIT_WINDOW: i64 = 300;
+    
+    /// Fenêtre de reveal après le second engagement (5 minutes)
+    pub const REVEAL_WINDOW: i64 = 300;
+    
+    /// Nouvel état vide pour un round, avec sa deadline de commit
+    pub fn new(commit_deadline: i64) -> Self {
+        RPSGameState {
+            player1_commitment: [0u8; 32],  // Sera rempli lors du commit
+            player2_commitment: [0u8; 32],  // Sera rempli lors du commit
+            player1_revealed: None,
+            player2_revealed: None,
+            commit_deadline,
+            reveal_deadline: None,
+        }
+    }
+    
+    pub fn commitment(&self, seat: u8) -> &[u8; 32] {
+        if seat == UniversalMatch::CREATOR_SEAT {
+            &self.player1_commitment
+        } else {
+            &self.player2_commitment
+        }
+    }
+    
+    pub fn has_committed(&self, seat: u8) -> bool {
+        *self.commitment(seat) != [0u8; 32]
+    }
+    
+    pub fn both_committed(&self) -> bool {
+        self.has_committed(UniversalMatch::CREATOR_SEAT) &&
+        self.has_committed(UniversalMatch::OPPONENT_SEAT)
+    }
+    
+    pub fn revealed(&self, seat: u8) -> Option<RPSChoice> {
+        if seat == UniversalMatch::CREATOR_SEAT {
+            self.player1_revealed
+        } else {
+            self.player2_revealed
+        }
+    }
+    
+    pub fn both_revealed(&self) -> bool {
+        self.player1_revealed.is_some() && self.player2_revealed.is_some()
+    }
+    
+    /// Enregistre l'engagement d'un siège (une seule fois par round) et ouvre
+    /// la fenêtre de reveal au second engagement
+    pub fn commit(&mut self, seat: u8, commitment: [u8; 32], now: i64) -> Result<()> {
+        require!(now <= self.commit_deadline, RPSGameError::CommitDeadlinePassed);
+        require!(commitment != [0u8; 32], RPSGameError::InvalidMoveData);
+        require!(!self.has_committed(seat), RPSGameError::AlreadyCommitted);
+        
+        // Refuser la copie de l'engagement adverse (sinon match nul forcé au reveal)
+        let other_seat = UniversalMatch::other_seat(seat);
+        require!(*self.commitment(other_seat) != commitment, RPSGameError::DuplicateCommitment);
+        
+        if seat == UniversalMatch::CREATOR_SEAT {
+            self.player1_commitment = commitment;
+        } else {
+            self.player2_commitment = commitment;
+        }
+        
+        if self.both_committed() {
+            self.reveal_deadline = Some(now + Self::REVEAL_WINDOW);
+        }
+        Ok(())
+    }
+    
+    /// Vérifie le reveal contre l'engagement et enregistre le choix
+    pub fn reveal(&mut self, seat: u8, choice: RPSChoice, salt: &[u8; 32], now: i64) -> Result<()> {
+        let reveal_deadline = self.reveal_deadline.ok_or(RPSGameError::NotReadyToReveal)?;
+        require!(now <= reveal_deadline, RPSGameError::RevealDeadlinePassed);
+        require!(self.revealed(seat).is_none(), RPSGameError::AlreadyRevealed);
+        require!(
+            verify_commitment(self.commitment(seat), choice, salt),
+            RPSGameError::CommitmentVerificationFailed
+        );
+        
+        if seat == UniversalMatch::CREATOR_SEAT {
+            self.player1_revealed = Some(choice);
+        } else {
+            self.player2_revealed = Some(choice);
+        }
+        Ok(())
+    }
+    
+    /// Un siège a rempli sa part s'il a révélé, ou s'il a commis alors que
+    /// l'adversaire n'a jamais commis (il ne pouvait donc pas révéler)
+    pub fn fulfilled(&self, seat: u8) -> bool {
+        self.revealed(seat).is_some() || (
+            self.has_committed(seat) &&
+            !self.has_committed(UniversalMatch::other_seat(seat))
+        )
+    }
+    
+    /// La phase en cours a expiré: commit sans les deux engagements, ou reveal
+    pub fn phase_expired(&self, now: i64) -> bool {
+        match self.reveal_deadline {
+            Some(deadline) => now > deadline,
+            None => now > self.commit_deadline,
+        }
+    }
+    
+    /// Le round peut être réglé quand les deux choix sont révélés ou la phase expirée
+    pub fn is_settleable(&self, now: i64) -> bool {
+        self.both_revealed() || self.phase_expired(now)
+    }
+    
+    /// Siège gagnant par forfait: les deux joueurs ont commis, la fenêtre de
+    /// reveal est passée et un seul a révélé
+    pub fn forfeit_winner(&self, now: i64) -> Option<u8> {
+        let reveal_deadline = self.reveal_deadline?;
+        if now <= reveal_deadline {
+            return None;
+        }
+        
+        match (
+            self.revealed(UniversalMatch::CREATOR_SEAT),
+            self.revealed(UniversalMatch::OPPONENT_SEAT),
+        ) {
+            (Some(_), None) => Some(UniversalMatch::CREATOR_SEAT),
+            (None, Some(_)) => Some(UniversalMatch::OPPONENT_SEAT),
+            _ => None,
+        }
+    }
+    
+    /// Données compactes du round pour l'historique du RoundManager
+    pub fn round_data(&self) -> Vec<u8> {
+        vec![
+            self.player1_revealed.map_or(u8::MAX, |c| c as u8),
+            self.player2_revealed.map_or(u8::MAX, |c| c as u8),
+        ]
+    }
+    
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut bytes = Vec::new();
         bytes.extend_from_slice(&self.player1_commitment);
@@ -122,12 +356,23 @@ impl RPSGameState {
             }
         }
         
-        bytes.extend_from_slice(&self.reveal_deadline.to_le_bytes());
+        bytes.extend_from_slice(&self.commit_deadline.to_le_bytes());
+        
+        match self.reveal_deadline {
+            Some(deadline) => {
+                bytes.push(1); // Has value
+                bytes.extend_from_slice(&deadline.to_le_bytes());
+            },
+            None => {
+                bytes.push(0); // No value
+                bytes.extend_from_slice(&[0u8; 8]);
+            }
+        }
         bytes
     }
     
     pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
-        require!(bytes.len() >= 76, RPSGameError::InvalidStateData);
+        require!(bytes.len() >= 85, RPSGameError::InvalidStateData);
         
         let mut player1_commitment = [0u8; 32];
         player1_commitment.copy_from_slice(&bytes[0..32]);
@@ -147,15 +392,22 @@ impl RPSGameState {
             None
         };
         
-        let reveal_deadline = i64::from_le_bytes(
+        let commit_deadline = i64::from_le_bytes(
             bytes[68..76].try_into().unwrap()
         );
         
+        let reveal_deadline = if bytes[76] == 1 {
+            Some(i64::from_le_bytes(bytes[77..85].try_into().unwrap()))
+        } else {
+            None
+        };
+        
         Ok(RPSGameState {
             player1_commitment,
             player2_commitment,
             player1_revealed,
             player2_revealed,
+            commit_deadline,
             reveal_deadline,
         })
     }
@@ -212,4 +464,112 @@ pub enum RPSGameError {
     
     #[msg("Not ready to reveal")]
     NotReadyToReveal,
-}
\ No newline at end of file
+    
+    #[msg("Already committed")]
+    AlreadyCommitted,
+    
+    #[msg("Commitment identical to the opponent's")]
+    DuplicateCommitment,
+    
+    #[msg("Commit deadline passed")]
+    CommitDeadlinePassed,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const CREATOR: u8 = UniversalMatch::CREATOR_SEAT;
+    const OPPONENT: u8 = UniversalMatch::OPPONENT_SEAT;
+    
+    fn salt(byte: u8) -> [u8; 32] {
+        [byte; 32]
+    }
+    
+    /// Les deux joueurs commettent à t=10 et t=20 (commit jusqu'à t=300)
+    fn committed_state() -> RPSGameState {
+        let mut state = RPSGameState::new(RPSGameState::COMMIT_WINDOW);
+        state.commit(CREATOR, create_commitment(RPSChoice::Rock, &salt(1)), 10).unwrap();
+        state.commit(OPPONENT, create_commitment(RPSChoice::Paper, &salt(2)), 20).unwrap();
+        state
+    }
+    
+    #[test]
+    fn reveal_window_opens_only_once_both_players_committed() {
+        let mut state = RPSGameState::new(RPSGameState::COMMIT_WINDOW);
+        state.commit(CREATOR, create_commitment(RPSChoice::Rock, &salt(1)), 10).unwrap();
+        
+        assert_eq!(state.reveal_deadline, None);
+        assert_eq!(
+            state.reveal(CREATOR, RPSChoice::Rock, &salt(1), 11).unwrap_err(),
+            RPSGameError::NotReadyToReveal.into()
+        );
+        
+        // Le second engagement arrive tard: la fenêtre de reveal part de lui
+        state.commit(OPPONENT, create_commitment(RPSChoice::Paper, &salt(2)), 290).unwrap();
+        assert_eq!(state.reveal_deadline, Some(290 + RPSGameState::REVEAL_WINDOW));
+        state.reveal(CREATOR, RPSChoice::Rock, &salt(1), 400).unwrap();
+    }
+    
+    #[test]
+    fn commit_is_refused_after_the_commit_deadline() {
+        let mut state = RPSGameState::new(RPSGameState::COMMIT_WINDOW);
+        
+        assert_eq!(
+            state.commit(CREATOR, create_commitment(RPSChoice::Rock, &salt(1)), 301).unwrap_err(),
+            RPSGameError::CommitDeadlinePassed.into()
+        );
+        assert!(!state.is_settleable(300));
+        assert!(state.is_settleable(301));
+    }
+    
+    #[test]
+    fn reveal_is_refused_after_the_reveal_deadline() {
+        let mut state = committed_state();
+        let deadline = 20 + RPSGameState::REVEAL_WINDOW;
+        
+        state.reveal(CREATOR, RPSChoice::Rock, &salt(1), deadline).unwrap();
+        assert_eq!(
+            state.reveal(OPPONENT, RPSChoice::Paper, &salt(2), deadline + 1).unwrap_err(),
+            RPSGameError::RevealDeadlinePassed.into()
+        );
+    }
+    
+    #[test]
+    fn forfeit_goes_to_the_only_revealer_after_the_reveal_window() {
+        let mut state = committed_state();
+        let deadline = 20 + RPSGameState::REVEAL_WINDOW;
+        
+        // Personne n'a révélé: pas de forfait
+        assert_eq!(state.forfeit_winner(deadline + 1), None);
+        
+        state.reveal(OPPONENT, RPSChoice::Paper, &salt(2), 30).unwrap();
+        assert_eq!(state.forfeit_winner(deadline), None);
+        assert_eq!(state.forfeit_winner(deadline + 1), Some(OPPONENT));
+        
+        // Les deux ont révélé: le match se règle, pas de forfait
+        state.reveal(CREATOR, RPSChoice::Rock, &salt(1), 40).unwrap();
+        assert_eq!(state.forfeit_winner(deadline + 1), None);
+    }
+    
+    #[test]
+    fn commit_alone_never_wins_a_forfeit() {
+        let mut state = RPSGameState::new(RPSGameState::COMMIT_WINDOW);
+        state.commit(CREATOR, create_commitment(RPSChoice::Rock, &salt(1)), 10).unwrap();
+        
+        assert_eq!(state.forfeit_winner(1_000), None);
+    }
+    
+    #[test]
+    fn state_round_trips_through_bytes() {
+        let mut state = committed_state();
+        state.reveal(CREATOR, RPSChoice::Rock, &salt(1), 30).unwrap();
+        
+        let decoded = RPSGameState::from_bytes(&state.to_bytes()).unwrap();
+        assert_eq!(decoded.commit_deadline, state.commit_deadline);
+        assert_eq!(decoded.reveal_deadline, state.reveal_deadline);
+        assert_eq!(decoded.player1_revealed, Some(RPSChoice::Rock));
+        assert_eq!(decoded.player2_revealed, None);
+        assert_eq!(decoded.player2_commitment, state.player2_commitment);
+    }
+}
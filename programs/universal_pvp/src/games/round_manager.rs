@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::universal_engine::{GameResult, UniversalMatch, MatchStatus};
+use crate::universal_engine::{GameResult, RandomnessSource};
 
 // ===========================
 // SYSTÈME DE GESTION DES ROUNDS
@@ -37,6 +37,19 @@ pub struct RoundResult {
 }
 
 impl RoundManager {
+    /// Égalités consécutives avant résolution forcée
+    pub const MAX_CONSECUTIVE_DRAWS: u8 = 3;
+    /// Octets de round_data conservés par entrée d'historique (un choix par siège d'un duel)
+    pub const MAX_ROUND_DATA: usize = 2;
+    
+    /// Taille maximale de to_bytes() pour `total_rounds` rounds: chaque round
+    /// peut être précédé de MAX_CONSECUTIVE_DRAWS - 1 égalités rejouées, et la
+    /// dernière égalité force la résolution
+    pub fn max_len(total_rounds: u8) -> usize {
+        let entries = total_rounds as usize * Self::MAX_CONSECUTIVE_DRAWS as usize;
+        8 + entries * (1 + 1 + 8 + 1 + Self::MAX_ROUND_DATA)
+    }
+    
     /// Créer un nouveau gestionnaire de rounds
     pub fn new(total_rounds: u8, max_consecutive_draws: u8) -> Self {
         let rounds_to_win = (total_rounds / 2) + 1;
@@ -58,12 +71,13 @@ impl RoundManager {
         &mut self, 
         result: GameResult,
         timestamp: i64,
-        round_data: Vec<u8>,
+        mut round_data: Vec<u8>,
     ) -> RoundOutcome {
-        // Enregistrer le round dans l'historique
+        // Enregistrer le round dans l'historique (données tronquées pour tenir dans round_state)
+        round_data.truncate(Self::MAX_ROUND_DATA);
         self.round_history.push(RoundResult {
             round_number: self.rounds_played + 1,
-            result: result.clone(),
+            result,
             timestamp,
             round_data,
         });
@@ -163,40 +177,36 @@ impl RoundManager {
     }
     
     /// Forcer une résolution en cas de trop d'égalités
-    pub fn forced_resolution(&self) -> GameResult {
+    pub fn forced_resolution(&self, randomness: &dyn RandomnessSource) -> Result<GameResult> {
         // Utiliser plusieurs critères pour déterminer le gagnant
         
         // 1. D'abord vérifier le score
         if self.player1_score > self.player2_score {
-            return GameResult::Player1Wins;
+            return Ok(GameResult::Player1Wins);
         } else if self.player2_score > self.player1_score {
-            return GameResult::Player2Wins;
+            return Ok(GameResult::Player2Wins);
         }
         
-        // 2. Si égalité parfaite, utiliser un tirage pseudo-aléatoire
-        // basé sur les timestamps des rounds pour la fairness
-        let hash_sum: i64 = self.round_history
-            .iter()
-            .map(|r| r.timestamp)
-            .sum();
-        
-        if hash_sum % 2 == 0 {
-            GameResult::Player1Wins
+        // 2. Si égalité parfaite, tirage au sort sur l'aléatoire commun des joueurs
+        let domain = [b"forced_resolution".as_slice(), &[self.rounds_played]].concat();
+        if randomness.random_below(&domain, 2)? == 0 {
+            Ok(GameResult::Player1Wins)
         } else {
-            GameResult::Player2Wins
+            Ok(GameResult::Player2Wins)
         }
     }
     
     /// Sérialiser l'état du gestionnaire de rounds
     pub fn to_bytes(&self) -> Vec<u8> {
-        let mut bytes = Vec::new();
-        bytes.push(self.total_rounds);
-        bytes.push(self.rounds_played);
-        bytes.push(self.player1_score);
-        bytes.push(self.player2_score);
-        bytes.push(self.rounds_to_win);
-        bytes.push(self.consecutive_draws);
-        bytes.push(self.max_consecutive_draws);
+        let mut bytes = vec![
+            self.total_rounds,
+            self.rounds_played,
+            self.player1_score,
+            self.player2_score,
+            self.rounds_to_win,
+            self.consecutive_draws,
+            self.max_consecutive_draws,
+        ];
         
         // Ajouter l'historique
         bytes.push(self.round_history.len() as u8);
@@ -1,13 +1,23 @@
+// Le code généré par #[program] (anchor 0.31) appelle AccountInfo::realloc, déprécié
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer as SplTransfer};
+use anchor_lang::system_program;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_fee::TransferFeeAmount, BaseStateWithExtensions, StateWithExtensions},
+};
+use anchor_spl::token_interface::{
+    self, CloseAccount, HarvestWithheldTokensToMint, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
 
 // Modules
 pub mod universal_engine;
 pub mod games;
 
 use universal_engine::*;
-use games::rock_paper_scissors::*;
-use games::GameLogic;
+use games::{CommitRevealMove, GameContext, GameRouter};
 
 declare_id!("4bdQ9U3yXD9EY2SxxMVasPmp5gw7RLtnc8yTkFZovmRR");
 
@@ -28,24 +38,37 @@ pub mod universal_pvp {
         registry.total_games = 0;
         registry.active_games = Vec::new();
         registry.paused = false;
+        registry.randomness_oracle = None;
         
         msg!("🎮 Universal PvP Registry initialized with 0% fees!");
         Ok(())
     }
     
-    /// Enregistre un nouveau type de jeu
+    /// Enregistre un nouveau type de jeu (autorité du registre uniquement)
     pub fn register_game(
         ctx: Context<RegisterGame>,
         game_type: GameType,
         name: String,
+        config_bounds: GameConfigBounds,
     ) -> Result<()> {
         let registry = &mut ctx.accounts.game_registry;
         let clock = Clock::get()?;
         
+        // Les bornes de table font foi: seul l'opérateur enregistre un jeu,
+        // une seule fois par type
+        require!(
+            ctx.accounts.creator.key() == registry.authority,
+            UniversalGameError::Unauthorized
+        );
+        require!(
+            registry.active_games.iter().all(|game| game.game_type != game_type),
+            UniversalGameError::GameAlreadyRegistered
+        );
         require!(
             registry.total_games < GameRegistry::MAX_GAMES as u32,
             UniversalGameError::RegistryFull
         );
+        config_bounds.validate(GameRouter::get_handler(game_type)?.manages_rounds())?;
         
         let game_def = GameDefinition {
             game_id: registry.total_games,
@@ -56,6 +79,7 @@ pub mod universal_pvp {
             total_matches: 0,
             is_active: true,
             game_token: None, // Sera ajouté plus tard si un token est créé
+            config_bounds,
         };
         
         registry.active_games.push(game_def);
@@ -75,6 +99,129 @@ pub mod universal_pvp {
         Ok(())
     }
 
+    /// Configure l'oracle autorisé à remplir les requêtes d'aléatoire (VRF)
+    pub fn set_randomness_oracle(
+        ctx: Context<SetRandomnessOracle>,
+        oracle: Option<Pubkey>,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.game_registry;
+        
+        require!(
+            ctx.accounts.authority.key() == registry.authority,
+            UniversalGameError::Unauthorized
+        );
+        
+        registry.randomness_oracle = oracle;
+        
+        msg!("🎲 Randomness oracle set to {:?}", oracle);
+        Ok(())
+    }
+    
+    /// Crée la file de matchmaking d'un palier (GameType, mint, mise)
+    pub fn initialize_match_queue(
+        ctx: Context<InitializeMatchQueue>,
+        game_type: GameType,
+        token_mint: Option<Pubkey>,
+        bet_amount: u64,
+    ) -> Result<()> {
+        // Le palier doit correspondre à un jeu actif du registre
+        ctx.accounts.game_registry.find_game(game_type)?;
+        
+        let queue = &mut ctx.accounts.match_queue;
+        queue.game_type = game_type;
+        queue.token_mint = token_mint;
+        queue.bet_amount = bet_amount;
+        queue.entries = Vec::new();
+        queue.bump = ctx.bumps.match_queue;
+        
+        msg!("📋 Match queue created: {:?} / {:?} / {}", game_type, token_mint, bet_amount);
+        Ok(())
+    }
+    
+    /// Met à jour les limites de table d'un jeu du registre
+    pub fn set_game_config_bounds(
+        ctx: Context<SetGameConfigBounds>,
+        game_id: u32,
+        config_bounds: GameConfigBounds,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.game_registry;
+        
+        require!(
+            ctx.accounts.authority.key() == registry.authority,
+            UniversalGameError::Unauthorized
+        );
+        
+        let game_def = registry
+            .active_games
+            .iter_mut()
+            .find(|game| game.game_id == game_id)
+            .ok_or(UniversalGameError::GameNotRegistered)?;
+        config_bounds.validate(GameRouter::get_handler(game_def.game_type)?.manages_rounds())?;
+        game_def.config_bounds = config_bounds;
+        
+        msg!("📏 Config bounds updated for game {}", game_id);
+        Ok(())
+    }
+    
+    /// Autorise un mint pour les mises, avec ses limites
+    pub fn add_allowed_mint(
+        ctx: Context<AddAllowedMint>,
+        min_bet: u64,
+        max_bet: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.game_registry.authority,
+            UniversalGameError::Unauthorized
+        );
+        AllowedMint::validate_limits(min_bet, max_bet)?;
+        
+        let allowed_mint = &mut ctx.accounts.allowed_mint;
+        allowed_mint.mint = ctx.accounts.token_mint.key();
+        allowed_mint.decimals = ctx.accounts.token_mint.decimals;
+        allowed_mint.min_bet = min_bet;
+        allowed_mint.max_bet = max_bet;
+        allowed_mint.enabled = true;
+        allowed_mint.bump = ctx.bumps.allowed_mint;
+        
+        msg!("🪙 Mint {} allowed: bets {} - {}", allowed_mint.mint, min_bet, max_bet);
+        Ok(())
+    }
+    
+    /// Met à jour les limites d'un mint autorisé, ou le suspend
+    pub fn update_allowed_mint(
+        ctx: Context<UpdateAllowedMint>,
+        min_bet: u64,
+        max_bet: u64,
+        enabled: bool,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.game_registry.authority,
+            UniversalGameError::Unauthorized
+        );
+        AllowedMint::validate_limits(min_bet, max_bet)?;
+        
+        let allowed_mint = &mut ctx.accounts.allowed_mint;
+        allowed_mint.min_bet = min_bet;
+        allowed_mint.max_bet = max_bet;
+        allowed_mint.enabled = enabled;
+        
+        msg!("🪙 Mint {} updated: bets {} - {}, enabled: {}", allowed_mint.mint, min_bet, max_bet, enabled);
+        Ok(())
+    }
+    
+    /// Retire un mint de la liste: les matchs existants ne sont pas affectés
+    pub fn remove_allowed_mint(
+        ctx: Context<RemoveAllowedMint>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.game_registry.authority,
+            UniversalGameError::Unauthorized
+        );
+        
+        msg!("🪙 Mint {} removed from the allowlist", ctx.accounts.allowed_mint.mint);
+        Ok(())
+    }
+
     // ===========================
     // CRÉATION DE MATCH (0% FRAIS!)
     // ===========================
@@ -85,50 +232,133 @@ pub mod universal_pvp {
         game_type: GameType,
         bet_amount: u64,
         game_config: GameConfig,
+        invited_opponent: Option<Pubkey>,
+        join_code_hash: Option<[u8; 32]>,
     ) -> Result<()> {
         let match_account = &mut ctx.accounts.match_account;
         let creator = &ctx.accounts.creator;
         let clock = Clock::get()?;
         
-        // Validations
+        // Validations: la config demandée doit respecter les bornes du registre
+        let game_def = ctx.accounts.game_registry.find_game(game_type)?;
+        let game = GameRouter::get_handler(game_type)?;
+        game.validate_config(&game_config, &game_def.config_bounds)?;
         require!(bet_amount >= game_config.min_bet, UniversalGameError::InsufficientBet);
         require!(bet_amount <= game_config.max_bet, UniversalGameError::BetTooLarge);
         
+        // Un match en tokens n'accepte que les mints autorisés par le registre
+        if let Some(token_mint) = ctx.accounts.token_mint.as_ref() {
+            let allowed_mint = ctx
+                .accounts
+                .allowed_mint
+                .as_ref()
+                .ok_or(UniversalGameError::MintNotAllowed)?;
+            require_keys_eq!(allowed_mint.mint, token_mint.key(), UniversalGameError::MintNotAllowed);
+            require!(
+                allowed_mint.decimals == token_mint.decimals,
+                UniversalGameError::MintMismatch
+            );
+            allowed_mint.check_bet(bet_amount)?;
+        }
+        
+        // Le jeu doit savoir arbitrer la table, et seul un duel passe par le RoundManager
+        require!(
+            game_config.max_players <= game.max_seats(),
+            UniversalGameError::ConfigOutOfBounds
+        );
+        require!(
+            game_config.max_players == 2 || game_config.rounds == 1 || game.manages_rounds(),
+            UniversalGameError::ConfigOutOfBounds
+        );
+        
+        // Une invitation désigne l'unique adversaire d'un duel
+        require!(
+            invited_opponent != Some(creator.key()),
+            UniversalGameError::InvalidGameState
+        );
+        require!(
+            invited_opponent.is_none() || game_config.max_players == 2,
+            UniversalGameError::InvalidGameState
+        );
+        
         // Initialiser le match avec 0% de frais!
         match_account.match_id = match_account.key();
         match_account.game_type = game_type;
         match_account.creator = creator.key();
-        match_account.opponent = None;
+        match_account.seats = vec![Seat::new(creator.key(), bet_amount)];
         match_account.bet_amount = bet_amount;
         match_account.token_mint = ctx.accounts.token_mint.as_ref().map(|m| m.key());
-        match_account.total_pot = bet_amount * 2; // Pot total SANS FRAIS!
+        match_account.total_pot = bet_amount; // Grossit à chaque siège pris, SANS FRAIS!
         match_account.status = MatchStatus::WaitingForOpponent;
         match_account.game_state = Vec::new();
         match_account.winner = None;
+        match_account.outcome = None;
+        match_account.payouts = Vec::new();
+        match_account.current_round = 0;
+        match_account.randomness_request = None;
+        match_account.seed_reveal_deadline = None;
         match_account.created_at = clock.unix_timestamp;
         match_account.started_at = None;
         match_account.ended_at = None;
         match_account.timeout_seconds = 300; // 5 minutes par défaut
         match_account.game_config = game_config;
         match_account.vault_bump = ctx.bumps.vault;
+        match_account.bump = ctx.bumps.match_account;
+        match_account.invited_opponent = invited_opponent;
+        match_account.join_code_hash = join_code_hash;
+        
+        // Le prochain match du créateur aura une nouvelle adresse
+        let profile = &mut ctx.accounts.player_profile;
+        if profile.player == Pubkey::default() {
+            profile.player = creator.key();
+            profile.bump = ctx.bumps.player_profile;
+        }
+        match_account.match_nonce = profile.match_nonce;
+        profile.match_nonce = profile
+            .match_nonce
+            .checked_add(1)
+            .ok_or(UniversalGameError::InvalidGameState)?;
+        
+        // Lister le match dans la file de matchmaking de son palier
+        match_account.queue = None;
+        if let Some(queue) = ctx.accounts.match_queue.as_mut() {
+            // Un match privé n'est jamais proposé aux inconnus
+            require!(!match_account.is_private(), UniversalGameError::MatchInviteOnly);
+            require!(queue.accepts(match_account), UniversalGameError::QueueMismatch);
+            queue.prune_expired(clock.unix_timestamp);
+            queue.push(QueueEntry {
+                match_id: match_account.key(),
+                creator: creator.key(),
+                created_at: clock.unix_timestamp,
+                expires_at: clock.unix_timestamp + match_account.timeout_seconds,
+            })?;
+            match_account.queue = Some(queue.key());
+        }
         
         // Transférer le pari du créateur vers le vault
-        if let Some(token_mint) = &ctx.accounts.token_mint {
-            // Transfer SPL tokens
-            let cpi_accounts = SplTransfer {
-                from: ctx.accounts.creator_token_account.as_ref().unwrap().to_account_info(),
-                to: ctx.accounts.vault_token_account.as_ref().unwrap().to_account_info(),
-                authority: creator.to_account_info(),
-            };
-            let cpi_program = ctx.accounts.token_program.as_ref().unwrap().to_account_info();
-            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-            token::transfer(cpi_ctx, bet_amount)?;
+        let stake = if let Some(mint) = match_account.token_mint {
+            // Transfer SPL tokens vers l'ATA du vault
+            let token_accounts = vault_token_accounts(
+                &ctx.accounts.vault.key(),
+                &mint,
+                ctx.accounts.vault_token_account.as_ref(),
+                ctx.accounts.token_mint.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+            )?;
+            let from = player_token_account(ctx.accounts.creator_token_account.as_ref(), &creator.key(), &mint)?;
+            deposit_to_vault(from, creator.to_account_info(), &token_accounts, bet_amount)?
         } else {
-            // Transfer SOL
+            // Transfer SOL, plus la réserve de rent du vault: il reste exempté
+            // jusqu'au dernier paiement, et la réserve revient au créateur à la fermeture
+            let rent_reserve = ctx
+                .accounts
+                .rent
+                .minimum_balance(0)
+                .saturating_sub(ctx.accounts.vault.lamports());
             let transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
                 &creator.key(),
                 &ctx.accounts.vault.key(),
-                bet_amount,
+                bet_amount + rent_reserve,
             );
             anchor_lang::solana_program::program::invoke(
                 &transfer_instruction,
@@ -138,7 +368,12 @@ pub mod universal_pvp {
                     ctx.accounts.system_program.to_account_info(),
                 ],
             )?;
-        }
+            bet_amount
+        };
+        
+        // Le siège et le pot ne comptent que ce qui est arrivé dans le vault
+        match_account.seats[UniversalMatch::CREATOR_SEAT as usize].stake = stake;
+        match_account.total_pot = stake;
         
         emit!(MatchCreated {
             match_id: match_account.key(),
@@ -158,6 +393,7 @@ pub mod universal_pvp {
     
     pub fn join_match(
         ctx: Context<JoinMatch>,
+        join_code: Option<Vec<u8>>,
     ) -> Result<()> {
         let match_account = &mut ctx.accounts.match_account;
         let opponent = &ctx.accounts.opponent;
@@ -169,24 +405,22 @@ pub mod universal_pvp {
             UniversalGameError::MatchAlreadyStarted
         );
         
-        require!(
-            match_account.opponent.is_none(),
-            UniversalGameError::MatchAlreadyStarted
-        );
+        // Match privé: invité désigné et/ou code de partie
+        match_account.check_invite(&opponent.key(), join_code.as_deref())?;
         
-        // Transférer le pari de l'opposant vers le vault
+        // Transférer le pari du joueur vers le vault
         let bet_amount = match_account.bet_amount;
-        
-        if let Some(token_mint) = &match_account.token_mint {
-            // Transfer SPL tokens
-            let cpi_accounts = SplTransfer {
-                from: ctx.accounts.opponent_token_account.as_ref().unwrap().to_account_info(),
-                to: ctx.accounts.vault_token_account.as_ref().unwrap().to_account_info(),
-                authority: opponent.to_account_info(),
-            };
-            let cpi_program = ctx.accounts.token_program.as_ref().unwrap().to_account_info();
-            let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-            token::transfer(cpi_ctx, bet_amount)?;
+        let stake = if let Some(mint) = match_account.token_mint {
+            // Transfer SPL tokens vers l'ATA du vault
+            let token_accounts = vault_token_accounts(
+                &ctx.accounts.vault.key(),
+                &mint,
+                ctx.accounts.vault_token_account.as_ref(),
+                ctx.accounts.token_mint.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+            )?;
+            let from = player_token_account(ctx.accounts.opponent_token_account.as_ref(), &opponent.key(), &mint)?;
+            deposit_to_vault(from, opponent.to_account_info(), &token_accounts, bet_amount)?
         } else {
             // Transfer SOL
             let transfer_instruction = anchor_lang::solana_program::system_instruction::transfer(
@@ -202,21 +436,58 @@ pub mod universal_pvp {
                     ctx.accounts.system_program.to_account_info(),
                 ],
             )?;
+            bet_amount
+        };
+        
+        // Prendre le prochain siège libre (refusé si déjà assis ou table pleine),
+        // misé de ce qui est réellement arrivé dans le vault
+        let seat = match_account.add_seat(opponent.key(), stake)?;
+        
+        emit!(MatchJoined {
+            match_id: match_account.key(),
+            opponent: opponent.key(),
+            seat,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        // Le match démarre dès que la dernière place est prise
+        if !match_account.is_full() {
+            msg!("🪑 {} took seat {} ({}/{} players)", 
+                opponent.key(), 
+                seat, 
+                match_account.seats.len(), 
+                match_account.game_config.max_players
+            );
+            return Ok(());
+        }
+        
+        // Retirer le match de la file où il était listé
+        if let Some(queue_key) = match_account.queue {
+            let queue = ctx
+                .accounts
+                .match_queue
+                .as_mut()
+                .ok_or(UniversalGameError::QueueAccountRequired)?;
+            require_keys_eq!(queue.key(), queue_key, UniversalGameError::QueueMismatch);
+            queue.remove(&match_account.key());
+            match_account.queue = None;
         }
         
         // Mettre à jour le statut du match
-        match_account.opponent = Some(opponent.key());
         match_account.status = MatchStatus::InProgress;
         match_account.started_at = Some(clock.unix_timestamp);
+        match_account.current_round = 1;
         
-        // Initialiser le système de rounds si nécessaire
-        if match_account.game_config.rounds > 1 {
+        let game = GameRouter::get_handler(match_account.game_type)?;
+        
+        // Initialiser le système de rounds si nécessaire (duels uniquement)
+        if match_account.game_config.rounds > 1 && !game.manages_rounds() {
             use games::round_manager::RoundManager;
             
-            // Créer un gestionnaire de rounds avec un maximum de 5 égalités consécutives
+            // Créer un gestionnaire de rounds, borné pour tenir dans round_state
             let round_manager = RoundManager::new(
                 match_account.game_config.rounds,
-                5 // max_consecutive_draws
+                RoundManager::MAX_CONSECUTIVE_DRAWS
             );
             
             // Sérialiser et stocker dans round_state
@@ -226,72 +497,50 @@ pub mod universal_pvp {
                 match_account.game_config.rounds);
         }
         
-        // Initialiser le game_state selon le type de jeu
-        match match_account.game_type {
-            GameType::RockPaperScissors => {
-                use games::rock_paper_scissors::RPSGameState;
-                
-                // Initialiser l'état RPS avec commit-reveal
-                let rps_state = RPSGameState {
-                    player1_commitment: [0u8; 32],  // Sera rempli lors du commit
-                    player2_commitment: [0u8; 32],  // Sera rempli lors du commit
-                    player1_revealed: None,
-                    player2_revealed: None,
-                    reveal_deadline: clock.unix_timestamp + 300, // 5 minutes pour révéler
-                };
-                
-                // Sérialiser et stocker dans game_state
-                match_account.game_state = rps_state.to_bytes();
-                
-                msg!("✂️ RPS commit-reveal system initialized");
-            },
-            GameType::Dice => {
-                // Pour Dice, initialiser avec un état simple
-                match_account.game_state = vec![0; 16]; // 16 bytes pour l'état du jeu de dés
-                msg!("🎲 Dice game state initialized");
-            },
-            GameType::CoinFlip => {
-                // État simple pour pile ou face
-                match_account.game_state = vec![0; 8]; // 8 bytes suffisent
-                msg!("🪙 Coin flip game state initialized");
-            },
-            GameType::HighCard => {
-                // État pour le jeu de carte haute
-                match_account.game_state = vec![0; 32]; // 32 bytes pour les cartes
-                msg!("🃏 High card game state initialized");
-            },
-            GameType::Custom(_id) => {
-                // État générique pour jeux custom
-                match_account.game_state = vec![0; 64]; // 64 bytes génériques
-                msg!("🎮 Custom game state initialized");
-            },
-        }
+        // Initialiser le game_state via le module du jeu
+        game.initialize_state(match_account, &GameContext::new(clock.unix_timestamp, None))?;
         
-        // Émettre l'événement de match rejoint
-        emit!(MatchJoined {
+        emit!(MatchStarted {
             match_id: match_account.key(),
-            opponent: opponent.key(),
+            players: match_account.seats.iter().map(|s| s.player).collect(),
+            total_pot: match_account.total_pot,
             timestamp: clock.unix_timestamp,
         });
         
-        msg!("⚔️ Match joined! {} vs {} - {} with 0% fees!", 
-            match_account.creator, 
-            opponent.key(),
-            match match_account.game_type {
-                GameType::RockPaperScissors => "Rock Paper Scissors",
-                GameType::Dice => "Dice Game",
-                GameType::CoinFlip => "Coin Flip",
-                GameType::HighCard => "High Card",
-                GameType::Custom(id) => {
-                    msg!("Custom Game ID: {}", id);
-                    "Custom Game"
-                },
-            }
+        msg!("⚔️ Match started! {} players - {} ({:?}) with 0% fees!", 
+            match_account.seats.len(),
+            game.name(),
+            match_account.game_type
         );
         
         Ok(())
     }
     
+    /// Rejoint le plus ancien match ouvert de la file (hors ses propres matchs).
+    /// Le client passe ce match, lu dans `match_queue.entries`
+    pub fn quick_join(
+        ctx: Context<JoinMatch>,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        let queue = ctx
+            .accounts
+            .match_queue
+            .as_ref()
+            .ok_or(UniversalGameError::QueueAccountRequired)?;
+        
+        let oldest = queue
+            .oldest_for(&ctx.accounts.opponent.key(), clock.unix_timestamp)
+            .ok_or(UniversalGameError::NoMatchAvailable)?;
+        require_keys_eq!(
+            ctx.accounts.match_account.key(),
+            oldest.match_id,
+            UniversalGameError::QueueMismatch
+        );
+        
+        // Les matchs privés ne sont jamais listés: pas de code à fournir
+        join_match(ctx, None)
+    }
+    
     // ===========================
     // SOUMETTRE UN MOUVEMENT
     // ===========================
@@ -302,41 +551,67 @@ pub mod universal_pvp {
     ) -> Result<()> {
         let match_account = &mut ctx.accounts.match_account;
         let player = &ctx.accounts.player;
+        let clock = Clock::get()?;
         
         require!(
             match_account.status == MatchStatus::InProgress,
             UniversalGameError::InvalidGameState
         );
         
-        // Valider le mouvement selon le type de jeu
-        match match_account.game_type {
-            GameType::RockPaperScissors => {
-                let rps = RockPaperScissors;
-                rps.validate_move(&move_data)?;
-            },
-            GameType::Dice => {
-                use games::dice::DiceGame;
-                let dice = DiceGame;
-                dice.validate_move(&move_data)?;
-            },
-            _ => return Err(UniversalGameError::InvalidGameType.into()),
-        }
+        // Résoudre le signataire vers son siège: seuls les joueurs du match peuvent jouer
+        let seat = match_account
+            .seat_of(&player.key())
+            .ok_or(UniversalGameError::Unauthorized)?;
         
-        // Stocker le mouvement dans game_state
-        match_account.game_state.extend_from_slice(&move_data);
+        // Valider et appliquer le mouvement via le module du jeu
+        let game = GameRouter::get_handler(match_account.game_type)?;
+        
+        // Un choix en clair serait lisible par l'adversaire avant qu'il ne joue
+        require!(
+            !game.supports_commit_reveal(),
+            UniversalGameError::CommitRevealRequired
+        );
+        game.validate_move(&move_data)?;
+        require!(
+            game.can_make_move(&player.key(), match_account),
+            UniversalGameError::MoveAlreadySubmitted
+        );
+        
+        // Le jeu écrit le mouvement dans le slot du siège (créateur d'abord, puis opposant)
+        game.process_move(
+            &player.key(),
+            &move_data,
+            match_account,
+            &GameContext::new(clock.unix_timestamp, None),
+        )?;
+        
+        emit!(MoveSubmitted {
+            match_id: match_account.key(),
+            player: player.key(),
+            seat,
+            round: match_account.current_round,
+            timestamp: clock.unix_timestamp,
+        });
         
-        msg!("📝 Move submitted by {}", player.key());
+        msg!("📝 Move submitted by {} (seat {}, round {})", 
+            player.key(), 
+            seat, 
+            match_account.current_round
+        );
         Ok(())
     }
     
     // ===========================
-    // DÉTERMINER LE GAGNANT
+    // COMMIT-REVEAL (RPS)
     // ===========================
     
-    pub fn settle_match(
-        ctx: Context<SettleMatch>,
+    /// Enregistre l'engagement sha256(mouvement || salt) du joueur pour le round en cours
+    pub fn commit_move(
+        ctx: Context<CommitMove>,
+        commitment: [u8; 32],
     ) -> Result<()> {
         let match_account = &mut ctx.accounts.match_account;
+        let player = &ctx.accounts.player;
         let clock = Clock::get()?;
         
         require!(
@@ -344,232 +619,685 @@ pub mod universal_pvp {
             UniversalGameError::InvalidGameState
         );
         
-        // Déterminer le résultat du round actuel selon le type de jeu
-        let round_result = match match_account.game_type {
-            GameType::RockPaperScissors => {
-                let rps = RockPaperScissors;
-                rps.determine_winner(match_account)?
-            },
-            GameType::Dice => {
-                use games::dice::DiceGame;
-                let dice = DiceGame;
-                dice.determine_winner(match_account)?
-            },
-            GameType::CoinFlip => {
-                // Pour CoinFlip, implémenter une logique simple basée sur game_state
-                require!(match_account.game_state.len() >= 2, UniversalGameError::InvalidGameState);
-                let player1_choice = match_account.game_state[0]; // 0 = heads, 1 = tails
-                let player2_choice = match_account.game_state[1];
-                
-                // Simuler un tirage au sort pseudo-aléatoire basé sur le timestamp
-                let coin_result = (clock.unix_timestamp % 2) as u8;
-                
-                if player1_choice == coin_result && player2_choice != coin_result {
-                    GameResult::Player1Wins
-                } else if player2_choice == coin_result && player1_choice != coin_result {
-                    GameResult::Player2Wins
-                } else {
-                    GameResult::Draw
-                }
-            },
-            GameType::HighCard => {
-                // Pour HighCard, utiliser les 2 premiers bytes comme cartes
-                require!(match_account.game_state.len() >= 2, UniversalGameError::InvalidGameState);
-                let player1_card = match_account.game_state[0];
-                let player2_card = match_account.game_state[1];
-                
-                if player1_card > player2_card {
-                    GameResult::Player1Wins
-                } else if player2_card > player1_card {
-                    GameResult::Player2Wins
-                } else {
-                    GameResult::Draw
-                }
-            },
-            GameType::Custom(_) => {
-                // Pour les jeux custom, utiliser une logique basique
-                require!(match_account.game_state.len() >= 2, UniversalGameError::InvalidGameState);
-                let player1_value = match_account.game_state[0];
-                let player2_value = match_account.game_state[1];
-                
-                if player1_value > player2_value {
-                    GameResult::Player1Wins
-                } else if player2_value > player1_value {
-                    GameResult::Player2Wins
-                } else {
-                    GameResult::Draw
-                }
-            },
-        };
+        let game = GameRouter::get_handler(match_account.game_type)?;
+        require!(game.supports_commit_reveal(), UniversalGameError::InvalidGameType);
         
-        // Gérer les rounds multiples si configurés
-        if match_account.game_config.rounds > 1 && !match_account.round_state.is_empty() {
-            use games::round_manager::RoundManager;
-            
-            // Restaurer le gestionnaire de rounds
-            let mut round_manager = RoundManager::from_bytes(&match_account.round_state)?;
-            
-            // Traiter le résultat du round actuel
-            let round_outcome = round_manager.process_round_result(
-                round_result,
-                clock.unix_timestamp,
-                match_account.game_state.clone(),
-            );
-            
-            // Sauvegarder l'état mis à jour
-            match_account.round_state = round_manager.to_bytes();
-            
-            match round_outcome {
-                games::round_manager::RoundOutcome::ContinueMatch => {
-                    // Le match continue, réinitialiser les états de jeu pour le prochain round
-                    match match_account.game_type {
-                        GameType::RockPaperScissors => {
-                            use games::rock_paper_scissors::RPSGameState;
-                            let rps_state = RPSGameState {
-                                player1_commitment: [0u8; 32],
-                                player2_commitment: [0u8; 32],
-                                player1_revealed: None,
-                                player2_revealed: None,
-                                reveal_deadline: clock.unix_timestamp + 300,
-                            };
-                            match_account.game_state = rps_state.to_bytes();
-                        },
-                        GameType::Dice => {
-                            match_account.game_state = vec![0; 16];
-                        },
-                        GameType::CoinFlip => {
-                            match_account.game_state = vec![0; 8];
-                        },
-                        GameType::HighCard => {
-                            match_account.game_state = vec![0; 32];
-                        },
-                        GameType::Custom(_) => {
-                            match_account.game_state = vec![0; 64];
-                        },
-                    }
-                    
-                    let progress = round_manager.get_match_status();
-                    msg!("🎯 Round {} completed. Score: {} - {}. Match continues...", 
-                        progress.rounds_played, 
-                        progress.player1_score, 
-                        progress.player2_score
-                    );
-                    
-                    return Ok(());
-                },
-                
-                games::round_manager::RoundOutcome::DrawRequiresReplay => {
-                    // Égalité, préparer pour un rejeu immédiat
-                    match match_account.game_type {
-                        GameType::RockPaperScissors => {
-                            use games::rock_paper_scissors::RPSGameState;
-                            let rps_state = RPSGameState {
-                                player1_commitment: [0u8; 32],
-                                player2_commitment: [0u8; 32],
-                                player1_revealed: None,
-                                player2_revealed: None,
-                                reveal_deadline: clock.unix_timestamp + 300,
-                            };
-                            match_account.game_state = rps_state.to_bytes();
-                        },
-                        _ => {
-                            // Réinitialiser l'état pour les autres jeux
-                            match_account.game_state = vec![0; match_account.game_state.len()];
-                        }
-                    }
-                    
-                    msg!("⚖️ Round draw! Replay required. Consecutive draws: {}", 
-                        round_manager.consecutive_draws
-                    );
-                    
-                    return Ok(());
-                },
-                
-                games::round_manager::RoundOutcome::MatchWon(final_result) => {
-                    // Un joueur a gagné le match complet
-                    let winner = match final_result {
-                        GameResult::Player1Wins => Some(match_account.creator),
-                        GameResult::Player2Wins => match_account.opponent,
-                        _ => None,
-                    };
-                    
-                    match_account.winner = winner;
-                    match_account.status = MatchStatus::Completed;
-                    match_account.ended_at = Some(clock.unix_timestamp);
-                    
-                    let progress = round_manager.get_match_status();
-                    emit!(MatchCompleted {
-                        match_id: match_account.key(),
-                        winner,
-                        total_pot: match_account.total_pot,
-                        game_result: final_result,
-                        timestamp: clock.unix_timestamp,
-                    });
-                    
-                    msg!("🏆 Match completed! Final score: {} - {}. Winner: {:?}", 
-                        progress.player1_score, 
-                        progress.player2_score, 
-                        winner
-                    );
-                    
-                    return Ok(());
-                },
-                
-                games::round_manager::RoundOutcome::ForcedResolution => {
-                    // Trop d'égalités ou résolution forcée
-                    let forced_result = round_manager.forced_resolution();
-                    let winner = match forced_result {
-                        GameResult::Player1Wins => Some(match_account.creator),
-                        GameResult::Player2Wins => match_account.opponent,
-                        _ => None,
-                    };
-                    
-                    match_account.winner = winner;
-                    match_account.status = MatchStatus::Completed;
-                    match_account.ended_at = Some(clock.unix_timestamp);
-                    
-                    emit!(MatchCompleted {
-                        match_id: match_account.key(),
-                        winner,
-                        total_pot: match_account.total_pot,
-                        game_result: forced_result,
-                        timestamp: clock.unix_timestamp,
-                    });
-                    
-                    msg!("⚖️ Match resolved by forced resolution after {} consecutive draws. Winner: {:?}", 
-                        round_manager.max_consecutive_draws, 
-                        winner
-                    );
-                    
-                    return Ok(());
-                }
-            }
-        } else {
-            // Match à round unique
-            let winner = match round_result {
-                GameResult::Player1Wins => Some(match_account.creator),
-                GameResult::Player2Wins => match_account.opponent,
-                GameResult::Draw => None, // Égalité, partage 50/50 dans claim_winnings
-                GameResult::Cancelled => None,
-            };
-            
-            match_account.winner = winner;
-            match_account.status = MatchStatus::Completed;
-            match_account.ended_at = Some(clock.unix_timestamp);
-            
-            emit!(MatchCompleted {
-                match_id: match_account.key(),
-                winner,
-                total_pot: match_account.total_pot,
-                game_result: round_result,
-                timestamp: clock.unix_timestamp,
-            });
-            
-            msg!("✅ Single-round match settled! Result: {:?}", round_result);
-        }
+        let seat = match_account
+            .seat_of(&player.key())
+            .ok_or(UniversalGameError::Unauthorized)?;
+        require!(
+            game.can_make_move(&player.key(), match_account),
+            UniversalGameError::MoveNotAllowed
+        );
         
-        Ok(())
-    }
+        let move_data = CommitRevealMove::Commit(commitment).try_to_vec()?;
+        game.process_move(
+            &player.key(),
+            &move_data,
+            match_account,
+            &GameContext::new(clock.unix_timestamp, None),
+        )?;
+        
+        emit!(MoveSubmitted {
+            match_id: match_account.key(),
+            player: player.key(),
+            seat,
+            round: match_account.current_round,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("🔒 Move committed by {} (seat {}, round {})", 
+            player.key(), 
+            seat, 
+            match_account.current_round
+        );
+        Ok(())
+    }
+    
+    /// Révèle le choix et le salt; vérifie qu'ils correspondent à l'engagement
+    pub fn reveal_move(
+        ctx: Context<RevealMove>,
+        choice: u8,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        let match_account = &mut ctx.accounts.match_account;
+        let player = &ctx.accounts.player;
+        let clock = Clock::get()?;
+        
+        require!(
+            match_account.status == MatchStatus::InProgress,
+            UniversalGameError::InvalidGameState
+        );
+        
+        let game = GameRouter::get_handler(match_account.game_type)?;
+        require!(game.supports_commit_reveal(), UniversalGameError::InvalidGameType);
+        
+        let seat = match_account
+            .seat_of(&player.key())
+            .ok_or(UniversalGameError::Unauthorized)?;
+        require!(
+            game.can_make_move(&player.key(), match_account),
+            UniversalGameError::MoveNotAllowed
+        );
+        
+        let move_data = CommitRevealMove::Reveal {
+            move_data: vec![choice],
+            salt,
+        }.try_to_vec()?;
+        game.process_move(
+            &player.key(),
+            &move_data,
+            match_account,
+            &GameContext::new(clock.unix_timestamp, None),
+        )?;
+        
+        emit!(MoveRevealed {
+            match_id: match_account.key(),
+            player: player.key(),
+            seat,
+            round: match_account.current_round,
+            move_data: vec![choice],
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("🔓 Move revealed by {} (seat {}): {}", player.key(), seat, choice);
+        Ok(())
+    }
+    
+    // ===========================
+    // ALÉATOIRE (SEEDS + VRF)
+    // ===========================
+    
+    /// Engage la seed du joueur: sha256(seed). Les jeux de hasard combinent
+    /// les seeds de tous les sièges, donc personne ne peut choisir l'issue seul
+    pub fn commit_seed(
+        ctx: Context<CommitSeed>,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        let match_account = &mut ctx.accounts.match_account;
+        let player = &ctx.accounts.player;
+        let clock = Clock::get()?;
+        
+        require!(
+            match_account.status == MatchStatus::InProgress,
+            UniversalGameError::InvalidGameState
+        );
+        // Les jeux à commit-reveal tirent leur entropie des salts de reveal_move
+        require!(
+            !GameRouter::get_handler(match_account.game_type)?.supports_commit_reveal(),
+            UniversalGameError::InvalidGameType
+        );
+        require!(commitment != [0u8; 32], UniversalGameError::InvalidGameState);
+        
+        let seat = match_account
+            .seat_of(&player.key())
+            .ok_or(UniversalGameError::Unauthorized)?;
+        
+        require!(
+            !match_account.seats[seat as usize].entropy.has_committed(),
+            UniversalGameError::SeedAlreadyCommitted
+        );
+        // Copier l'engagement d'un autre siège annulerait sa contribution
+        require!(
+            match_account.seats.iter().all(|s| s.entropy.commitment != commitment),
+            UniversalGameError::SeedAlreadyCommitted
+        );
+        
+        match_account.seats[seat as usize].entropy.commitment = commitment;
+        
+        // Dernier engagement: la fenêtre de reveal s'ouvre. Passé la deadline,
+        // claim_seed_forfeit paie ceux qui ont révélé
+        if match_account.seeds_committed() {
+            match_account.seed_reveal_deadline = Some(clock.unix_timestamp + UniversalMatch::SEED_REVEAL_WINDOW);
+        }
+        
+        msg!("🔒 Seed committed by {} (seat {})", player.key(), seat);
+        Ok(())
+    }
+    
+    /// Révèle la seed une fois que tous les sièges ont commis la leur
+    pub fn reveal_seed(
+        ctx: Context<RevealSeed>,
+        seed: [u8; 32],
+    ) -> Result<()> {
+        let match_account = &mut ctx.accounts.match_account;
+        let player = &ctx.accounts.player;
+        let clock = Clock::get()?;
+        
+        require!(
+            match_account.status == MatchStatus::InProgress,
+            UniversalGameError::InvalidGameState
+        );
+        
+        let seat = match_account
+            .seat_of(&player.key())
+            .ok_or(UniversalGameError::Unauthorized)?;
+        
+        let deadline = match_account
+            .seed_reveal_deadline
+            .ok_or(UniversalGameError::RandomnessNotReady)?;
+        require!(
+            clock.unix_timestamp <= deadline,
+            UniversalGameError::SeedRevealDeadlinePassed
+        );
+        
+        // La sortie VRF doit être fixée avant toute seed révélée: sinon l'oracle
+        // pourrait attendre les seeds et choisir l'issue
+        if let Some(request_key) = match_account.randomness_request {
+            let request = ctx
+                .accounts
+                .randomness_request
+                .as_ref()
+                .ok_or(UniversalGameError::RandomnessAccountMismatch)?;
+            require_keys_eq!(request.key(), request_key, UniversalGameError::RandomnessAccountMismatch);
+            require!(
+                request.randomness.is_some(),
+                UniversalGameError::RandomnessNotReady
+            );
+        }
+        
+        let entropy = &mut match_account.seats[seat as usize].entropy;
+        require!(entropy.seed.is_none(), UniversalGameError::InvalidSeedReveal);
+        require!(
+            SeatEntropy::commitment_for(&seed) == entropy.commitment,
+            UniversalGameError::InvalidSeedReveal
+        );
+        entropy.seed = Some(seed);
+        
+        emit!(SeedRevealed {
+            match_id: match_account.key(),
+            player: player.key(),
+            seat,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("🔓 Seed revealed by {} (seat {})", player.key(), seat);
+        Ok(())
+    }
+    
+    /// Lance les dés de chaque siège pour le prochain round de Dice, depuis
+    /// les seeds révélées (et le VRF éventuel). N'importe quel siège peut lancer
+    pub fn roll_dice(
+        ctx: Context<RollDice>,
+    ) -> Result<()> {
+        let match_account = &mut ctx.accounts.match_account;
+        let player = &ctx.accounts.player;
+        let clock = Clock::get()?;
+        
+        require!(
+            match_account.status == MatchStatus::InProgress,
+            UniversalGameError::InvalidGameState
+        );
+        let game = GameRouter::get_handler(match_account.game_type)?;
+        require!(game.supports_roll(), UniversalGameError::InvalidGameType);
+        require!(
+            match_account.seat_of(&player.key()).is_some(),
+            UniversalGameError::Unauthorized
+        );
+        
+        let game_ctx = GameContext::new(
+            clock.unix_timestamp,
+            ctx.accounts.randomness_request.as_deref(),
+        );
+        let dice = game.roll(match_account, &game_ctx)?;
+        
+        msg!("🎲 Dice rolled: {:?}", dice);
+        
+        emit!(DiceRolled {
+            match_id: match_account.key(),
+            dice,
+            timestamp: clock.unix_timestamp,
+        });
+        Ok(())
+    }
+    
+    /// Ajoute une requête VRF au match; sa sortie sera mélangée aux seeds.
+    /// Doit être faite avant qu'une seed ne soit révélée, et remplie avant
+    /// qu'aucune ne puisse l'être
+    pub fn request_randomness(
+        ctx: Context<RequestRandomness>,
+    ) -> Result<()> {
+        let match_account = &mut ctx.accounts.match_account;
+        let request = &mut ctx.accounts.randomness_request;
+        let registry = &ctx.accounts.game_registry;
+        let payer = &ctx.accounts.payer;
+        let clock = Clock::get()?;
+        
+        require!(
+            match_account.status == MatchStatus::WaitingForOpponent ||
+            match_account.status == MatchStatus::InProgress,
+            UniversalGameError::InvalidGameState
+        );
+        require!(
+            match_account.seat_of(&payer.key()).is_some(),
+            UniversalGameError::Unauthorized
+        );
+        require!(
+            match_account.randomness_request.is_none() &&
+            match_account.seats.iter().all(|s| s.entropy.seed.is_none()),
+            UniversalGameError::RandomnessAlreadyRequested
+        );
+        
+        let oracle = registry.randomness_oracle
+            .ok_or(UniversalGameError::RandomnessOracleNotSet)?;
+        
+        request.match_id = match_account.key();
+        request.oracle = oracle;
+        request.randomness = None;
+        request.requested_at = clock.unix_timestamp;
+        request.fulfilled_at = None;
+        request.bump = ctx.bumps.randomness_request;
+        
+        match_account.randomness_request = Some(request.key());
+        
+        msg!("🎲 Randomness requested from oracle {}", oracle);
+        Ok(())
+    }
+    
+    /// Appelé par l'oracle pour remplir la requête VRF
+    pub fn fulfill_randomness(
+        ctx: Context<FulfillRandomness>,
+        randomness: [u8; 32],
+    ) -> Result<()> {
+        let request = &mut ctx.accounts.randomness_request;
+        let oracle = &ctx.accounts.oracle;
+        let clock = Clock::get()?;
+        
+        require!(
+            oracle.key() == request.oracle,
+            UniversalGameError::Unauthorized
+        );
+        require!(
+            request.randomness.is_none(),
+            UniversalGameError::RandomnessAlreadyFulfilled
+        );
+        
+        request.randomness = Some(randomness);
+        request.fulfilled_at = Some(clock.unix_timestamp);
+        
+        emit!(RandomnessFulfilled {
+            match_id: request.match_id,
+            oracle: oracle.key(),
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("🎲 Randomness fulfilled for match {}", request.match_id);
+        Ok(())
+    }
+    
+    // ===========================
+    // FORFAIT APRÈS DEADLINE DE REVEAL
+    // ===========================
+    
+    /// Attribue le pot au joueur qui a commis et révélé quand l'autre, après
+    /// avoir commis, n'a pas révélé avant la fin de la fenêtre de reveal
+    pub fn claim_timeout_forfeit(
+        ctx: Context<ClaimTimeoutForfeit>,
+    ) -> Result<()> {
+        let match_account = &mut ctx.accounts.match_account;
+        let claimer = &ctx.accounts.claimer;
+        let clock = Clock::get()?;
+        
+        require!(
+            match_account.status == MatchStatus::InProgress,
+            UniversalGameError::InvalidGameState
+        );
+        require!(
+            match_account.seat_of(&claimer.key()).is_some(),
+            UniversalGameError::Unauthorized
+        );
+        
+        let game = GameRouter::get_handler(match_account.game_type)?;
+        let game_ctx = GameContext::new(clock.unix_timestamp, None);
+        let forfeit_seat = game
+            .forfeit_winner(match_account, &game_ctx)
+            .ok_or(UniversalGameError::NoForfeitToClaim)?;
+        
+        let timeout_type = if forfeit_seat == UniversalMatch::CREATOR_SEAT {
+            TimeoutType::OpponentNoReveal
+        } else {
+            TimeoutType::CreatorNoReveal
+        };
+        let outcome = MatchOutcome::Winner(forfeit_seat);
+        
+        // Le gagnant par forfait touche le pot au titre du forfait
+        let payouts = match_account
+            .outcome_payouts(&outcome)?
+            .into_iter()
+            .map(|payout| match payout.reason {
+                PayoutReason::Winner => Payout { reason: PayoutReason::Forfeit, ..payout },
+                _ => payout,
+            })
+            .collect();
+        match_account.complete(outcome, payouts, clock.unix_timestamp)?;
+        let winner = match_account.winner;
+        
+        emit!(MatchTimedOut {
+            match_id: match_account.key(),
+            timeout_type,
+            winner,
+            total_pot: match_account.total_pot,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("⏰ Reveal timeout: {:?}. Winner: {:?}", timeout_type, winner);
+        Ok(())
+    }
+    
+    /// Jeux de hasard: passé la deadline de reveal des seeds, le pot revient aux
+    /// sièges qui ont révélé (le dernier à révéler connaît l'issue et ne peut
+    /// pas se retirer gratuitement); si personne n'a révélé, chacun récupère sa mise
+    pub fn claim_seed_forfeit(
+        ctx: Context<ClaimSeedForfeit>,
+    ) -> Result<()> {
+        let match_account = &mut ctx.accounts.match_account;
+        let claimer = &ctx.accounts.claimer;
+        let clock = Clock::get()?;
+        
+        require!(
+            match_account.status == MatchStatus::InProgress,
+            UniversalGameError::InvalidGameState
+        );
+        require!(
+            !GameRouter::get_handler(match_account.game_type)?.supports_commit_reveal(),
+            UniversalGameError::InvalidGameType
+        );
+        require!(
+            match_account.seat_of(&claimer.key()).is_some(),
+            UniversalGameError::Unauthorized
+        );
+        
+        let deadline = match_account
+            .seed_reveal_deadline
+            .ok_or(UniversalGameError::RandomnessNotReady)?;
+        require!(
+            clock.unix_timestamp > deadline,
+            UniversalGameError::SeedRevealDeadlineNotPassed
+        );
+        require!(
+            !match_account.seeds_revealed(),
+            UniversalGameError::ShouldSettleNotTimeout
+        );
+        
+        // Les sièges qui ont révélé se partagent le pot au titre du forfait
+        let scores: Vec<u16> = match_account
+            .seats
+            .iter()
+            .map(|seat| u16::from(seat.entropy.seed.is_some()))
+            .collect();
+        let outcome = MatchOutcome::from_scores(&scores);
+        let payouts = match_account
+            .outcome_payouts(&outcome)?
+            .into_iter()
+            .map(|payout| match (&outcome, payout.reason) {
+                (MatchOutcome::Draw, _) => payout,
+                (_, PayoutReason::Winner | PayoutReason::Tie) => Payout { reason: PayoutReason::Forfeit, ..payout },
+                _ => payout,
+            })
+            .collect();
+        match_account.complete(outcome, payouts, clock.unix_timestamp)?;
+        let winner = match_account.winner;
+        
+        emit!(MatchTimedOut {
+            match_id: match_account.key(),
+            timeout_type: TimeoutType::SeedNoReveal,
+            winner,
+            total_pot: match_account.total_pot,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("⏰ Seed reveal timeout. Winner: {:?}", winner);
+        Ok(())
+    }
+    
+    // ===========================
+    // DÉTERMINER LE GAGNANT
+    // ===========================
+    
+    /// Règle le round en cours. Avec `auto_payout`, si le match se termine, les
+    /// parts sont versées depuis le vault aux comptes passés dans
+    /// `remaining_accounts` (un par paiement, dans l'ordre de `payouts`), puis
+    /// le vault et le match sont fermés au profit du créateur
+    pub fn settle_match<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleMatch<'info>>,
+        auto_payout: bool,
+    ) -> Result<()> {
+        let match_account = &mut ctx.accounts.match_account;
+        let clock = Clock::get()?;
+        
+        require!(
+            match_account.status == MatchStatus::InProgress,
+            UniversalGameError::InvalidGameState
+        );
+        
+        let game = GameRouter::get_handler(match_account.game_type)?;
+        let game_ctx = GameContext::new(
+            clock.unix_timestamp,
+            ctx.accounts.randomness_request.as_deref(),
+        );
+        
+        // Chaque siège doit avoir joué ce round (ou le jeu doit pouvoir trancher sans lui)
+        require!(
+            game.is_round_complete(match_account, &game_ctx),
+            UniversalGameError::MovesNotSubmitted
+        );
+        
+        // Gérer les rounds multiples si configurés (duels uniquement)
+        if match_account.game_config.rounds > 1 && !match_account.round_state.is_empty() {
+            use games::round_manager::RoundManager;
+            
+            // Déterminer le résultat du round actuel via le module du jeu
+            let round_result = game.determine_winner(match_account, &game_ctx)?;
+            
+            // Restaurer le gestionnaire de rounds
+            let mut round_manager = RoundManager::from_bytes(&match_account.round_state)?;
+            
+            // Traiter le résultat du round actuel
+            let round_data = game.round_data(match_account, &game_ctx)?;
+            let round_outcome = round_manager.process_round_result(
+                round_result,
+                clock.unix_timestamp,
+                round_data,
+            );
+            
+            // Sauvegarder l'état mis à jour
+            match_account.round_state = round_manager.to_bytes();
+            
+            match round_outcome {
+                games::round_manager::RoundOutcome::ContinueMatch => {
+                    // Le match continue, réinitialiser les états de jeu pour le prochain round
+                    game.reset_round(match_account, &game_ctx)?;
+                    match_account.advance_round();
+                    
+                    let progress = round_manager.get_match_status();
+                    msg!("🎯 Round {} completed. Score: {} - {}. Match continues...", 
+                        progress.rounds_played, 
+                        progress.player1_score, 
+                        progress.player2_score
+                    );
+                    
+                    return Ok(());
+                },
+                
+                games::round_manager::RoundOutcome::DrawRequiresReplay => {
+                    // Égalité, préparer pour un rejeu immédiat
+                    game.reset_round(match_account, &game_ctx)?;
+                    match_account.advance_round();
+                    
+                    msg!("⚖️ Round draw! Replay required. Consecutive draws: {}", 
+                        round_manager.consecutive_draws
+                    );
+                    
+                    return Ok(());
+                },
+                
+                games::round_manager::RoundOutcome::MatchWon(final_result) => {
+                    // Un joueur a gagné le match complet
+                    let outcome = MatchOutcome::from_result(final_result);
+                    let payouts = game.calculate_payouts(match_account, &outcome)?;
+                    match_account.complete(outcome.clone(), payouts, clock.unix_timestamp)?;
+                    let winner = match_account.winner;
+                    
+                    let progress = round_manager.get_match_status();
+                    emit!(MatchCompleted {
+                        match_id: match_account.key(),
+                        winner,
+                        total_pot: match_account.total_pot,
+                        outcome,
+                        payouts: match_account.payouts.clone(),
+                        timestamp: clock.unix_timestamp,
+                    });
+                    
+                    msg!("🏆 Match completed! Final score: {} - {}. Winner: {:?}", 
+                        progress.player1_score, 
+                        progress.player2_score, 
+                        winner
+                    );
+                },
+                
+                games::round_manager::RoundOutcome::ForcedResolution => {
+                    // Trop d'égalités ou résolution forcée
+                    let randomness = game_ctx.randomness(match_account)?;
+                    let forced_result = round_manager.forced_resolution(&randomness)?;
+                    let outcome = MatchOutcome::from_result(forced_result);
+                    let payouts = game.calculate_payouts(match_account, &outcome)?;
+                    match_account.complete(outcome.clone(), payouts, clock.unix_timestamp)?;
+                    let winner = match_account.winner;
+                    
+                    emit!(MatchCompleted {
+                        match_id: match_account.key(),
+                        winner,
+                        total_pot: match_account.total_pot,
+                        outcome,
+                        payouts: match_account.payouts.clone(),
+                        timestamp: clock.unix_timestamp,
+                    });
+                    
+                    msg!("⚖️ Match resolved by forced resolution after {} consecutive draws. Winner: {:?}", 
+                        round_manager.max_consecutive_draws, 
+                        winner
+                    );
+                }
+            }
+        } else {
+            // Match à round unique (ou rounds gérés par le jeu): résultat final,
+            // gagnant unique ou classement de tous les sièges
+            let outcome = game.determine_outcome(match_account, &game_ctx)?;
+            let payouts = game.calculate_payouts(match_account, &outcome)?;
+            match_account.complete(outcome.clone(), payouts, clock.unix_timestamp)?;
+            
+            emit!(MatchCompleted {
+                match_id: match_account.key(),
+                winner: match_account.winner,
+                total_pot: match_account.total_pot,
+                outcome: outcome.clone(),
+                payouts: match_account.payouts.clone(),
+                timestamp: clock.unix_timestamp,
+            });
+            
+            msg!("✅ Match settled! Outcome: {:?}", outcome);
+        }
+        
+        // Paiement automatique: tout se règle dans cette transaction
+        if auto_payout && match_account.status == MatchStatus::Completed {
+            let vault = ctx
+                .accounts
+                .vault
+                .as_ref()
+                .ok_or(UniversalGameError::MissingPayoutAccount)?
+                .to_account_info();
+            let creator = ctx
+                .accounts
+                .creator
+                .as_ref()
+                .ok_or(UniversalGameError::MissingPayoutAccount)?
+                .to_account_info();
+            let system_program = ctx
+                .accounts
+                .system_program
+                .as_ref()
+                .ok_or(UniversalGameError::MissingPayoutAccount)?
+                .to_account_info();
+            let token_accounts = match match_account.token_mint {
+                Some(mint) => Some(vault_token_accounts(
+                    vault.key,
+                    &mint,
+                    ctx.accounts.vault_token_account.as_ref(),
+                    ctx.accounts.token_mint.as_ref(),
+                    ctx.accounts.token_program.as_ref(),
+                )?),
+                None => None,
+            };
+            let destinations = payout_accounts(
+                ctx.remaining_accounts,
+                &match_account.payouts,
+                token_accounts.as_ref(),
+            )?;
+            
+            let match_key = match_account.key();
+            let seeds = &[
+                b"vault",
+                match_key.as_ref(),
+                &[match_account.vault_bump],
+            ];
+            let signer = &[&seeds[..]];
+            
+            for (index, destination) in destinations.iter().enumerate() {
+                let payout = match_account.payouts[index];
+                transfer_from_vault(
+                    &vault,
+                    destination,
+                    token_accounts.as_ref(),
+                    &system_program,
+                    signer,
+                    payout.amount,
+                    match_account.outstanding_liabilities(),
+                )?;
+                match_account.payouts[index].claimed = true;
+                
+                emit!(WinningsClaimed {
+                    match_id: match_key,
+                    claimer: payout.recipient,
+                    amount: payout.amount,
+                    timestamp: clock.unix_timestamp,
+                });
+            }
+            for seat in match_account.seats.iter_mut() {
+                seat.claimed = true;
+            }
+            
+            // Fermer le vault puis le match: la rent revient au créateur
+            let creator_token_account = match match_account.token_mint {
+                Some(mint) => Some(player_token_account(
+                    ctx.accounts.creator_token_account.as_ref(),
+                    &match_account.creator,
+                    &mint,
+                )?),
+                None => None,
+            };
+            close_vault(
+                &vault,
+                &creator,
+                token_accounts.as_ref(),
+                creator_token_account.as_ref(),
+                &system_program,
+                signer,
+            )?;
+            close_randomness_request(
+                match_account,
+                ctx.accounts.randomness_request.as_ref(),
+                &creator,
+            )?;
+            match_account.close(creator)?;
+            
+            msg!("💸 Auto payout: {} payouts sent, match {} closed", 
+                match_account.payouts.len(), 
+                match_key
+            );
+        }
+        
+        Ok(())
+    }
     
     // ===========================
     // RÉCLAMER LES GAINS
@@ -578,7 +1306,7 @@ pub mod universal_pvp {
     pub fn claim_winnings(
         ctx: Context<ClaimWinnings>,
     ) -> Result<()> {
-        let match_account = &ctx.accounts.match_account;
+        let match_account = &mut ctx.accounts.match_account;
         let vault = &mut ctx.accounts.vault;
         let claimer = &ctx.accounts.claimer;
         let clock = Clock::get()?;
@@ -595,66 +1323,53 @@ pub mod universal_pvp {
             UniversalGameError::InvalidGameState
         );
         
-        // Déterminer le montant à réclamer selon le résultat
-        let (claim_amount, is_winner) = if let Some(winner) = match_account.winner {
-            // Cas d'un gagnant unique - 100% du pot au gagnant (0% de frais!)
-            require!(
-                claimer.key() == winner,
-                UniversalGameError::Unauthorized
-            );
-            (match_account.total_pot, true) // 100% du pot total au gagnant!
-        } else {
-            // Cas d'égalité - chaque joueur récupère sa mise
-            require!(
-                claimer.key() == match_account.creator || 
-                Some(claimer.key()) == match_account.opponent,
-                UniversalGameError::Unauthorized
-            );
-            (match_account.bet_amount, false) // Chacun récupère sa mise originale
+        // Payer exactement les parts enregistrées au règlement pour ce joueur
+        // (100% du pot au gagnant, 0% de frais!), marquées réclamées avant le transfert
+        let is_winner = match_account.payouts.iter().any(|payout| {
+            payout.recipient == claimer.key() &&
+            matches!(payout.reason, PayoutReason::Winner | PayoutReason::Forfeit)
+        });
+        // Le vault doit couvrir tous les paiements encore dus, celui-ci compris
+        let liabilities = match_account.outstanding_liabilities();
+        let claim_amount = match_account.claim_payouts(&claimer.key())?;
+        
+        // Comptes SPL vérifiés: ATA du vault et compte du réclamant pour le mint du match
+        let token_accounts = match match_account.token_mint {
+            Some(mint) => Some(vault_token_accounts(
+                &vault.key(),
+                &mint,
+                ctx.accounts.vault_token_account.as_ref(),
+                ctx.accounts.token_mint.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+            )?),
+            None => None,
         };
         
-        // Vérifier qu'il y a assez de fonds dans le vault
-        if match_account.token_mint.is_some() {
-            // Pour SPL tokens, vérifier le solde du vault
-            let vault_token_account = ctx.accounts.vault_token_account.as_ref().unwrap();
-            require!(
-                vault_token_account.amount >= claim_amount,
-                UniversalGameError::InsufficientFunds
-            );
-        } else {
-            // Pour SOL, vérifier les lamports du vault
-            require!(
-                vault.lamports() >= claim_amount,
-                UniversalGameError::InsufficientFunds
-            );
-        }
+        // Transférer les fonds depuis le vault, solde vérifié avant le débit
+        let match_key = match_account.key();
+        let seeds = &[
+            b"vault",
+            match_key.as_ref(),
+            &[match_account.vault_bump],
+        ];
+        let signer = &[&seeds[..]];
+        let destination = match match_account.token_mint {
+            Some(mint) => player_token_account(ctx.accounts.claimer_token_account.as_ref(), &claimer.key(), &mint)?,
+            None => claimer.to_account_info(),
+        };
+        transfer_from_vault(
+            &vault.to_account_info(),
+            &destination,
+            token_accounts.as_ref(),
+            &ctx.accounts.system_program.to_account_info(),
+            signer,
+            claim_amount,
+            liabilities,
+        )?;
         
-        // Transférer les fonds depuis le vault
-        if let Some(_token_mint) = &match_account.token_mint {
-            // Transfer SPL tokens avec les seeds du vault
-            let match_key = match_account.key();
-            let seeds = &[
-                b"vault",
-                match_key.as_ref(),
-                &[match_account.vault_bump],
-            ];
-            let signer = &[&seeds[..]];
-            
-            let cpi_accounts = SplTransfer {
-                from: ctx.accounts.vault_token_account.as_ref().unwrap().to_account_info(),
-                to: ctx.accounts.claimer_token_account.as_ref().unwrap().to_account_info(),
-                authority: vault.to_account_info(),
-            };
-            let cpi_program = ctx.accounts.token_program.as_ref().unwrap().to_account_info();
-            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-            token::transfer(cpi_ctx, claim_amount)?;
-            
+        if match_account.token_mint.is_some() {
             msg!("💰 SPL Token transfer completed: {} tokens to {}", claim_amount, claimer.key());
         } else {
-            // Transfer SOL directement
-            **vault.try_borrow_mut_lamports()? -= claim_amount;
-            **claimer.try_borrow_mut_lamports()? += claim_amount;
-            
             msg!("💰 SOL transfer completed: {} lamports to {}", claim_amount, claimer.key());
         }
         
@@ -671,7 +1386,7 @@ pub mod universal_pvp {
             msg!("🏆 WINNER! {} claimed the full pot of {} (100% - no fees!)", 
                 claimer.key(), claim_amount);
         } else {
-            msg!("⚖️ DRAW: {} reclaimed their bet of {}", 
+            msg!("⚖️ DRAW: {} received their share of {}", 
                 claimer.key(), claim_amount);
         }
         
@@ -686,7 +1401,6 @@ pub mod universal_pvp {
         ctx: Context<CloseMatch>,
     ) -> Result<()> {
         let match_account = &ctx.accounts.match_account;
-        let vault = &ctx.accounts.vault;
         let authority = &ctx.accounts.authority;
         
         // Vérifications de sécurité
@@ -697,31 +1411,64 @@ pub mod universal_pvp {
         
         // Seuls les participants peuvent fermer le match
         require!(
-            authority.key() == match_account.creator || 
-            Some(authority.key()) == match_account.opponent,
+            match_account.seat_of(&authority.key()).is_some(),
             UniversalGameError::Unauthorized
         );
         
-        // Vérifier que le vault est vide (tous les gains ont été réclamés)
-        if match_account.token_mint.is_some() {
-            // Pour SPL tokens
-            let vault_token_account = ctx.accounts.vault_token_account.as_ref().unwrap();
-            require!(
-                vault_token_account.amount == 0,
-                UniversalGameError::InsufficientFunds // Il reste des fonds à réclamer
-            );
-        } else {
-            // Pour SOL, s'assurer que seuls les frais de rent restent
-            let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
-            require!(
-                vault.lamports() <= rent_exempt_minimum,
-                UniversalGameError::InsufficientFunds // Il reste des fonds à réclamer
-            );
-        }
+        // Chaque paiement du règlement doit avoir été réclamé par son destinataire
+        // (un solde de vault peut être gonflé par n'importe qui, pas ce registre)
+        require!(
+            match_account.all_payouts_claimed(),
+            UniversalGameError::UnclaimedPayouts
+        );
+        
+        // Fermer le compte de token du vault et balayer les lamports restants
+        // du PDA: la rent revient au créateur, qui l'a payée, pas à l'appelant
+        let token_accounts = match match_account.token_mint {
+            Some(mint) => Some(vault_token_accounts(
+                &ctx.accounts.vault.key(),
+                &mint,
+                ctx.accounts.vault_token_account.as_ref(),
+                ctx.accounts.token_mint.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+            )?),
+            None => None,
+        };
+        
+        let match_key = match_account.key();
+        let seeds = &[
+            b"vault",
+            match_key.as_ref(),
+            &[match_account.vault_bump],
+        ];
+        let signer = &[&seeds[..]];
+        
+        let creator_token_account = match match_account.token_mint {
+            Some(mint) => Some(player_token_account(
+                ctx.accounts.creator_token_account.as_ref(),
+                &match_account.creator,
+                &mint,
+            )?),
+            None => None,
+        };
+        
+        close_vault(
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.creator.to_account_info(),
+            token_accounts.as_ref(),
+            creator_token_account.as_ref(),
+            &ctx.accounts.system_program.to_account_info(),
+            signer,
+        )?;
+        close_randomness_request(
+            match_account,
+            ctx.accounts.randomness_request.as_ref(),
+            &ctx.accounts.creator.to_account_info(),
+        )?;
         
-        msg!("🗑️ Match {} closed successfully", match_account.key());
+        msg!("🗑️ Match {} closed successfully", match_key);
         
-        // Le compte sera automatiquement fermé par Anchor grâce à l'attribut close
+        // Le match est fermé par Anchor au profit du créateur (attribut close)
         Ok(())
     }
     
@@ -729,82 +1476,116 @@ pub mod universal_pvp {
     // ANNULER UN MATCH
     // ===========================
     
-    pub fn cancel_match(
-        ctx: Context<CancelMatch>,
+    pub fn cancel_match<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelMatch<'info>>,
     ) -> Result<()> {
         let match_account = &mut ctx.accounts.match_account;
         let vault = &mut ctx.accounts.vault;
         let requester = &ctx.accounts.requester;
         let clock = Clock::get()?;
         
-        // Vérifier si le match peut être annulé
-        require!(
-            match_account.status == MatchStatus::WaitingForOpponent ||
-            match_account.can_timeout(),
-            UniversalGameError::CannotCancel
-        );
-        
         // Vérifier l'autorisation
         require!(
-            requester.key() == match_account.creator ||
-            Some(requester.key()) == match_account.opponent,
+            match_account.seat_of(&requester.key()).is_some(),
             UniversalGameError::Unauthorized
         );
         
-        match_account.status = MatchStatus::Cancelled;
-        match_account.ended_at = Some(clock.unix_timestamp);
+        // Le match ne passe qu'une fois à Cancelled. Déjà annulé, il ne peut
+        // l'avoir été que par arbitrage (resolve_dispute), sans remboursement:
+        // ceux encore dus sont versés, un second appel échoue
+        let reason = if match_account.status == MatchStatus::Cancelled {
+            require!(match_account.has_pending_refunds(), UniversalGameError::AlreadyRefunded);
+            "dispute"
+        } else {
+            // Une fois la partie engagée (round enregistré, siège qui a joué, seeds
+            // engagées), arrêter de jouer ne doit pas rembourser: l'issue passe par
+            // settle_match, claim_timeout_forfeit ou claim_seed_forfeit
+            if match_account.status == MatchStatus::InProgress {
+                let game = GameRouter::get_handler(match_account.game_type)?;
+                require!(
+                    !game.has_progress(match_account),
+                    UniversalGameError::ForfeitRequired
+                );
+            }
+            
+            match_account.cancel(&requester.key(), clock.unix_timestamp)?
+        };
         
-        // Rembourser le créateur
-        let refund_amount = match_account.bet_amount;
+        // Retirer le match de la file où il était listé
+        if let Some(queue_key) = match_account.queue {
+            let queue = ctx
+                .accounts
+                .match_queue
+                .as_mut()
+                .ok_or(UniversalGameError::QueueAccountRequired)?;
+            require_keys_eq!(queue.key(), queue_key, UniversalGameError::QueueMismatch);
+            queue.remove(&match_account.key());
+            match_account.queue = None;
+        }
         
-        if match_account.token_mint.is_some() {
-            // Refund SPL tokens
-            let match_key = match_account.key();
-            let seeds = &[
-                b"vault",
-                match_key.as_ref(),
-                &[match_account.vault_bump],
-            ];
-            let signer = &[&seeds[..]];
+        // Rembourser chaque siège de sa mise, au joueur enregistré uniquement:
+        // créateur et opposant via les comptes nommés, sièges suivants via
+        // `remaining_accounts` dans l'ordre des sièges
+        let match_key = match_account.key();
+        let seeds = &[
+            b"vault",
+            match_key.as_ref(),
+            &[match_account.vault_bump],
+        ];
+        let signer = &[&seeds[..]];
+        let mint = match_account.token_mint;
+        let token_accounts = match mint {
+            Some(mint) => Some(vault_token_accounts(
+                &vault.key(),
+                &mint,
+                ctx.accounts.vault_token_account.as_ref(),
+                ctx.accounts.token_mint.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+            )?),
+            None => None,
+        };
+        let vault = vault.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        
+        for index in 0..match_account.seats.len() {
+            let seat = match_account.seats[index];
+            if seat.status == SeatStatus::Refunded {
+                continue;
+            }
             
-            let cpi_accounts = SplTransfer {
-                from: ctx.accounts.vault_token_account.as_ref().unwrap().to_account_info(),
-                to: ctx.accounts.creator_token_account.as_ref().unwrap().to_account_info(),
-                authority: vault.to_account_info(),
+            let destination = match (index, mint) {
+                // Refund SPL tokens
+                (0, Some(mint)) => player_token_account(ctx.accounts.creator_token_account.as_ref(), &seat.player, &mint)?,
+                (1, Some(mint)) => player_token_account(ctx.accounts.opponent_token_account.as_ref(), &seat.player, &mint)?,
+                // Refund SOL
+                (0, None) => recipient_account(&ctx.accounts.creator.to_account_info(), &seat.player, None)?,
+                (1, None) => recipient_account(
+                    &ctx.accounts
+                        .opponent
+                        .as_ref()
+                        .ok_or(UniversalGameError::SeatAccountMismatch)?
+                        .to_account_info(),
+                    &seat.player,
+                    None,
+                )?,
+                _ => seat_refund_account(ctx.remaining_accounts, index, &seat, token_accounts.as_ref())?,
             };
-            let cpi_program = ctx.accounts.token_program.as_ref().unwrap().to_account_info();
-            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-            token::transfer(cpi_ctx, refund_amount)?;
-            
-            // Si un opposant a rejoint, le rembourser aussi
-            if match_account.opponent.is_some() {
-                let cpi_accounts_opponent = SplTransfer {
-                    from: ctx.accounts.vault_token_account.as_ref().unwrap().to_account_info(),
-                    to: ctx.accounts.opponent_token_account.as_ref().unwrap().to_account_info(),
-                    authority: vault.to_account_info(),
-                };
-                let cpi_ctx_opponent = CpiContext::new_with_signer(
-                    ctx.accounts.token_program.as_ref().unwrap().to_account_info(),
-                    cpi_accounts_opponent,
-                    signer
-                );
-                token::transfer(cpi_ctx_opponent, refund_amount)?;
-            }
-        } else {
-            // Refund SOL
-            **vault.try_borrow_mut_lamports()? -= refund_amount;
-            **ctx.accounts.creator.try_borrow_mut_lamports()? += refund_amount;
+            transfer_from_vault(
+                &vault,
+                &destination,
+                token_accounts.as_ref(),
+                &system_program,
+                signer,
+                seat.stake,
+                match_account.outstanding_liabilities(),
+            )?;
             
-            // Si un opposant a rejoint, le rembourser aussi
-            if let Some(opponent_account) = &ctx.accounts.opponent {
-                **vault.try_borrow_mut_lamports()? -= refund_amount;
-                **opponent_account.try_borrow_mut_lamports()? += refund_amount;
-            }
+            match_account.seats[index].status = SeatStatus::Refunded;
         }
         
         emit!(MatchCancelled {
             match_id: match_account.key(),
-            reason: if match_account.can_timeout() { "timeout".to_string() } else { "no_opponent".to_string() },
+            reason: reason.to_string(),
             timestamp: clock.unix_timestamp,
         });
         
@@ -812,6 +1593,124 @@ pub mod universal_pvp {
         Ok(())
     }
     
+    // ===========================
+    // NETTOYAGE DES MATCHS ABANDONNÉS
+    // ===========================
+    
+    /// Ferme un match annulé, terminé et réclamé, ou jamais rejoint et expiré
+    /// depuis longtemps, ainsi que son vault. N'importe quel keeper peut
+    /// l'appeler: la rent revient au créateur, moins une petite prime au keeper
+    pub fn cleanup_match(
+        ctx: Context<CleanupMatch>,
+    ) -> Result<()> {
+        let match_account = &mut ctx.accounts.match_account;
+        let keeper = &ctx.accounts.keeper;
+        let creator = ctx.accounts.creator.to_account_info();
+        let clock = Clock::get()?;
+        
+        require!(
+            match_account.can_cleanup(clock.unix_timestamp),
+            UniversalGameError::CleanupNotAllowed
+        );
+        
+        // Un match jamais rejoint peut encore être listé dans sa file
+        if let Some(queue_key) = match_account.queue {
+            let queue = ctx
+                .accounts
+                .match_queue
+                .as_mut()
+                .ok_or(UniversalGameError::QueueAccountRequired)?;
+            require_keys_eq!(queue.key(), queue_key, UniversalGameError::QueueMismatch);
+            queue.remove(&match_account.key());
+            match_account.queue = None;
+        }
+        
+        let token_accounts = match match_account.token_mint {
+            Some(mint) => Some(vault_token_accounts(
+                &ctx.accounts.vault.key(),
+                &mint,
+                ctx.accounts.vault_token_account.as_ref(),
+                ctx.accounts.token_mint.as_ref(),
+                ctx.accounts.token_program.as_ref(),
+            )?),
+            None => None,
+        };
+        
+        let match_key = match_account.key();
+        let seeds = &[
+            b"vault",
+            match_key.as_ref(),
+            &[match_account.vault_bump],
+        ];
+        let signer = &[&seeds[..]];
+        let vault = ctx.accounts.vault.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let creator_token_account = match match_account.token_mint {
+            Some(mint) => Some(player_token_account(
+                ctx.accounts.creator_token_account.as_ref(),
+                &match_account.creator,
+                &mint,
+            )?),
+            None => None,
+        };
+        
+        // Match jamais rejoint: la mise du créateur dort encore dans le vault.
+        // En SOL elle part avec le balayage du vault, en SPL elle est rendue d'abord
+        if match_account.status == MatchStatus::WaitingForOpponent {
+            if let (Some(creator_token_account), Some(token_accounts)) =
+                (creator_token_account.as_ref(), token_accounts.as_ref())
+            {
+                let stake = match_account.seats[UniversalMatch::CREATOR_SEAT as usize].stake;
+                transfer_from_vault(
+                    &vault,
+                    creator_token_account,
+                    Some(token_accounts),
+                    &system_program,
+                    signer,
+                    stake,
+                    match_account.outstanding_liabilities(),
+                )?;
+            }
+            match_account.seats[UniversalMatch::CREATOR_SEAT as usize].status = SeatStatus::Refunded;
+        }
+        
+        close_vault(
+            &vault,
+            &creator,
+            token_accounts.as_ref(),
+            creator_token_account.as_ref(),
+            &system_program,
+            signer,
+        )?;
+        close_randomness_request(
+            match_account,
+            ctx.accounts.randomness_request.as_ref(),
+            &creator,
+        )?;
+        
+        // Prime du keeper tiers prélevée sur la rent du match, le reste au créateur
+        let bounty = if keeper.key() == match_account.creator {
+            0
+        } else {
+            UniversalMatch::CLEANUP_BOUNTY_LAMPORTS.min(match_account.to_account_info().lamports())
+        };
+        if bounty > 0 {
+            **match_account.to_account_info().try_borrow_mut_lamports()? -= bounty;
+            **keeper.to_account_info().try_borrow_mut_lamports()? += bounty;
+        }
+        match_account.close(creator)?;
+        
+        emit!(MatchCleanedUp {
+            match_id: match_key,
+            keeper: keeper.key(),
+            bounty,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        msg!("🧹 Match {} cleaned up by {} (bounty: {})", match_key, keeper.key(), bounty);
+        Ok(())
+    }
+    
     // ===========================
     // DISPUTER UN MATCH
     // ===========================
@@ -832,11 +1731,16 @@ pub mod universal_pvp {
         );
         
         require!(
-            disputer.key() == match_account.creator ||
-            Some(disputer.key()) == match_account.opponent,
+            match_account.seat_of(&disputer.key()).is_some(),
             UniversalGameError::Unauthorized
         );
         
+        // Une fois des gains versés, un autre résultat ne pourrait plus être payé
+        require!(
+            !match_account.any_payout_claimed(),
+            UniversalGameError::CannotDispute
+        );
+        
         // Marquer comme disputé
         match_account.status = MatchStatus::Disputed;
         
@@ -875,28 +1779,37 @@ pub mod universal_pvp {
             authority.key() == registry.authority,
             UniversalGameError::Unauthorized
         );
+        require!(
+            !match_account.any_payout_claimed(),
+            UniversalGameError::CannotDispute
+        );
         
-        // Appliquer la résolution
-        match resolution {
-            DisputeResolution::Player1Wins => {
-                match_account.winner = Some(match_account.creator);
-                match_account.status = MatchStatus::Completed;
-            },
-            DisputeResolution::Player2Wins => {
-                match_account.winner = match_account.opponent;
-                match_account.status = MatchStatus::Completed;
+        // Appliquer la résolution: les paiements suivent le résultat tranché
+        let outcome = match resolution {
+            DisputeResolution::Player1Wins => Some(MatchOutcome::Winner(UniversalMatch::CREATOR_SEAT)),
+            DisputeResolution::Player2Wins => Some(MatchOutcome::Winner(UniversalMatch::OPPONENT_SEAT)),
+            DisputeResolution::SeatWins(seat) => {
+                require!(
+                    (seat as usize) < match_account.seats.len(),
+                    UniversalGameError::InvalidGameState
+                );
+                Some(MatchOutcome::Winner(seat))
             },
-            DisputeResolution::Draw => {
-                match_account.winner = None;
-                match_account.status = MatchStatus::Completed;
+            DisputeResolution::Draw => Some(MatchOutcome::Draw),
+            DisputeResolution::Cancel => None,
+        };
+        
+        match outcome {
+            Some(outcome) => {
+                let payouts = match_account.outcome_payouts(&outcome)?;
+                match_account.complete(outcome, payouts, clock.unix_timestamp)?;
             },
-            DisputeResolution::Cancel => {
+            None => {
                 match_account.status = MatchStatus::Cancelled;
+                match_account.ended_at = Some(clock.unix_timestamp);
             },
         }
         
-        match_account.ended_at = Some(clock.unix_timestamp);
-        
         emit!(DisputeResolved {
             match_id: match_account.key(),
             resolution,
@@ -909,21 +1822,389 @@ pub mod universal_pvp {
     }
 }
 
-// ===========================
-// CONTEXTS
-// ===========================
+// ===========================
+// HELPERS
+// ===========================
+
+/// Comptes SPL du vault d'un match en tokens (Token ou Token-2022), vérifiés
+/// par `vault_token_accounts`
+struct VaultTokenAccounts<'info> {
+    vault_token_account: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    decimals: u8,
+    token_program: AccountInfo<'info>,
+}
+
+/// Comptes SPL du vault: le mint doit être celui du match et appartenir au
+/// programme de token fourni, le compte de token être l'ATA du PDA vault
+fn vault_token_accounts<'info>(
+    vault: &Pubkey,
+    mint: &Pubkey,
+    vault_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    token_mint: Option<&InterfaceAccount<'info, Mint>>,
+    token_program: Option<&Interface<'info, TokenInterface>>,
+) -> Result<VaultTokenAccounts<'info>> {
+    let vault_token_account = vault_token_account.ok_or(UniversalGameError::MissingTokenAccount)?;
+    let token_mint = token_mint.ok_or(UniversalGameError::MissingTokenAccount)?;
+    let token_program = token_program.ok_or(UniversalGameError::MissingTokenAccount)?;
+    
+    require_keys_eq!(token_mint.key(), *mint, UniversalGameError::MintMismatch);
+    require_keys_eq!(
+        *token_mint.to_account_info().owner,
+        token_program.key(),
+        UniversalGameError::MintMismatch
+    );
+    require_keys_eq!(vault_token_account.mint, *mint, UniversalGameError::MintMismatch);
+    require_keys_eq!(vault_token_account.owner, *vault, UniversalGameError::TokenOwnerMismatch);
+    require_keys_eq!(
+        vault_token_account.key(),
+        get_associated_token_address_with_program_id(vault, mint, &token_program.key()),
+        UniversalGameError::TokenOwnerMismatch
+    );
+    
+    Ok(VaultTokenAccounts {
+        vault_token_account: vault_token_account.to_account_info(),
+        mint: token_mint.to_account_info(),
+        decimals: token_mint.decimals,
+        token_program: token_program.to_account_info(),
+    })
+}
+
+/// Compte de token d'un joueur: mint du match et détenu par le joueur
+fn player_token_account<'info>(
+    token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    player: &Pubkey,
+    mint: &Pubkey,
+) -> Result<AccountInfo<'info>> {
+    let token_account = token_account.ok_or(UniversalGameError::MissingTokenAccount)?;
+    require_keys_eq!(token_account.mint, *mint, UniversalGameError::MintMismatch);
+    require_keys_eq!(token_account.owner, *player, UniversalGameError::TokenOwnerMismatch);
+    Ok(token_account.to_account_info())
+}
+
+/// Compte de remboursement d'un siège au-delà de l'opposant, lu dans
+/// `remaining_accounts`
+fn seat_refund_account<'info>(
+    remaining_accounts: &[AccountInfo<'info>],
+    index: usize,
+    seat: &Seat,
+    token_accounts: Option<&VaultTokenAccounts<'info>>,
+) -> Result<AccountInfo<'info>> {
+    let info = index
+        .checked_sub(2)
+        .and_then(|position| remaining_accounts.get(position))
+        .ok_or(UniversalGameError::SeatAccountMismatch)?;
+    recipient_account(info, &seat.player, token_accounts)
+}
+
+/// Vérifie qu'un compte peut recevoir les fonds de `recipient`: son wallet
+/// en SOL, ou en SPL un compte de token du mint du vault dont il est propriétaire
+fn recipient_account<'info>(
+    info: &AccountInfo<'info>,
+    recipient: &Pubkey,
+    token_accounts: Option<&VaultTokenAccounts<'info>>,
+) -> Result<AccountInfo<'info>> {
+    require!(info.is_writable, UniversalGameError::SeatAccountMismatch);
+    
+    match token_accounts {
+        Some(token_accounts) => {
+            require_keys_eq!(
+                *info.owner,
+                token_accounts.token_program.key(),
+                UniversalGameError::MissingTokenAccount
+            );
+            let token_account = TokenAccount::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            require_keys_eq!(token_account.mint, token_accounts.mint.key(), UniversalGameError::MintMismatch);
+            require_keys_eq!(token_account.owner, *recipient, UniversalGameError::TokenOwnerMismatch);
+        },
+        None => {
+            require_keys_eq!(info.key(), *recipient, UniversalGameError::SeatAccountMismatch);
+        },
+    }
+    
+    Ok(info.clone())
+}
+
+/// Comptes des destinataires du paiement automatique, un par paiement et dans
+/// l'ordre de `payouts`. Un compte manquant et un compte qui n'est pas celui
+/// du destinataire sont deux erreurs distinctes
+fn payout_accounts<'info>(
+    remaining_accounts: &[AccountInfo<'info>],
+    payouts: &[Payout],
+    token_accounts: Option<&VaultTokenAccounts<'info>>,
+) -> Result<Vec<AccountInfo<'info>>> {
+    require!(
+        remaining_accounts.len() >= payouts.len(),
+        UniversalGameError::MissingPayoutAccount
+    );
+    require!(
+        remaining_accounts.len() == payouts.len(),
+        UniversalGameError::SeatAccountMismatch
+    );
+    
+    payouts
+        .iter()
+        .zip(remaining_accounts)
+        .map(|(payout, info)| recipient_account(info, &payout.recipient, token_accounts))
+        .collect()
+}
+
+/// Dépose `amount` tokens d'un joueur dans l'ATA du vault et renvoie le
+/// montant réellement reçu: un mint Token-2022 à frais de transfert en retient
+/// une partie, et le pot ne doit compter que ce qui est arrivé
+fn deposit_to_vault<'info>(
+    from: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    token_accounts: &VaultTokenAccounts<'info>,
+    amount: u64,
+) -> Result<u64> {
+    let balance_before = token_balance(&token_accounts.vault_token_account)?;
+    
+    let cpi_accounts = TransferChecked {
+        from,
+        mint: token_accounts.mint.clone(),
+        to: token_accounts.vault_token_account.clone(),
+        authority,
+    };
+    let cpi_ctx = CpiContext::new(token_accounts.token_program.clone(), cpi_accounts);
+    token_interface::transfer_checked(cpi_ctx, amount, token_accounts.decimals)?;
+    
+    let received = token_balance(&token_accounts.vault_token_account)?
+        .checked_sub(balance_before)
+        .ok_or(UniversalGameError::InvalidGameState)?;
+    require!(received > 0, UniversalGameError::InsufficientBet);
+    Ok(received)
+}
+
+/// Solde d'un compte de token, relu dans ses données (à jour après une CPI)
+fn token_balance(info: &AccountInfo) -> Result<u64> {
+    Ok(TokenAccount::try_deserialize(&mut &info.try_borrow_data()?[..])?.amount)
+}
+
+/// Verse `amount` depuis le vault: transfert système signé par les seeds du
+/// PDA en SOL, ou `transfer_checked` signé par le vault en SPL (d'éventuels
+/// frais de transfert Token-2022 sont retenus chez le destinataire).
+/// `liabilities` = ce que le vault doit encore, ce versement compris: le solde
+/// disponible (hors réserve de rent en SOL) doit le couvrir avant tout débit
+fn transfer_from_vault<'info>(
+    vault: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    token_accounts: Option<&VaultTokenAccounts<'info>>,
+    system_program: &AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+    amount: u64,
+    liabilities: u64,
+) -> Result<()> {
+    let available = match token_accounts {
+        Some(token_accounts) => token_balance(&token_accounts.vault_token_account)?,
+        None => vault.lamports().saturating_sub(Rent::get()?.minimum_balance(0)),
+    };
+    require!(
+        amount <= liabilities && available >= liabilities,
+        UniversalGameError::InsufficientFunds
+    );
+    
+    match token_accounts {
+        Some(token_accounts) => transfer_tokens_from_vault(vault, destination, token_accounts, signer, amount),
+        None => transfer_lamports_from_vault(vault, destination, system_program, signer, amount),
+    }
+}
+
+/// `transfer_checked` depuis l'ATA du vault, signé par le vault
+fn transfer_tokens_from_vault<'info>(
+    vault: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    token_accounts: &VaultTokenAccounts<'info>,
+    signer: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = TransferChecked {
+        from: token_accounts.vault_token_account.clone(),
+        mint: token_accounts.mint.clone(),
+        to: destination.clone(),
+        authority: vault.clone(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_accounts.token_program.clone(), cpi_accounts, signer);
+    token_interface::transfer_checked(cpi_ctx, amount, token_accounts.decimals)
+}
+
+/// Transfert système depuis le vault PDA (compte système sans données), signé
+/// par ses seeds: le programme ne possède pas le vault et ne peut pas en
+/// modifier les lamports directement
+fn transfer_lamports_from_vault<'info>(
+    vault: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = system_program::Transfer {
+        from: vault.clone(),
+        to: destination.clone(),
+    };
+    system_program::transfer(
+        CpiContext::new_with_signer(system_program.clone(), cpi_accounts, signer),
+        amount,
+    )
+}
+
+/// Ferme le vault: le compte de token (vide) est fermé par CPI signée par le
+/// vault, puis les lamports restants du PDA (réserve de rent comprise) sont
+/// transférés vers `destination`.
+/// L'ATA du vault est publique: des tokens envoyés par n'importe qui y restent
+/// après les paiements, et sont reversés à `creator_token_account` avant la
+/// fermeture. Token-2022 refuse aussi de fermer un compte qui retient des frais
+/// de transfert: ils sont d'abord récoltés vers le mint (instruction sans permission)
+fn close_vault<'info>(
+    vault: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    token_accounts: Option<&VaultTokenAccounts<'info>>,
+    creator_token_account: Option<&AccountInfo<'info>>,
+    system_program: &AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+) -> Result<()> {
+    if let Some(token_accounts) = token_accounts {
+        let leftover = token_balance(&token_accounts.vault_token_account)?;
+        if leftover > 0 {
+            let creator_token_account = creator_token_account.ok_or(UniversalGameError::MissingTokenAccount)?;
+            transfer_tokens_from_vault(vault, creator_token_account, token_accounts, signer, leftover)?;
+        }
+        
+        if withheld_transfer_fees(&token_accounts.vault_token_account)? > 0 {
+            let cpi_accounts = HarvestWithheldTokensToMint {
+                token_program_id: token_accounts.token_program.clone(),
+                mint: token_accounts.mint.clone(),
+            };
+            token_interface::harvest_withheld_tokens_to_mint(
+                CpiContext::new(token_accounts.token_program.clone(), cpi_accounts),
+                vec![token_accounts.vault_token_account.clone()],
+            )?;
+        }
+        
+        let cpi_accounts = CloseAccount {
+            account: token_accounts.vault_token_account.clone(),
+            destination: destination.clone(),
+            authority: vault.clone(),
+        };
+        token_interface::close_account(CpiContext::new_with_signer(
+            token_accounts.token_program.clone(),
+            cpi_accounts,
+            signer,
+        ))?;
+    }
+    
+    let lamports = vault.lamports();
+    if lamports > 0 {
+        transfer_lamports_from_vault(vault, destination, system_program, signer, lamports)?;
+    }
+    Ok(())
+}
+
+/// Ferme la requête VRF du match au profit du créateur. Si le match en a une,
+/// elle doit être fournie: sa rent resterait sinon bloquée une fois le match fermé
+fn close_randomness_request<'info>(
+    match_account: &UniversalMatch,
+    randomness_request: Option<&Account<'info, RandomnessRequest>>,
+    creator: &AccountInfo<'info>,
+) -> Result<()> {
+    if let Some(request_key) = match_account.randomness_request {
+        let request = randomness_request.ok_or(UniversalGameError::RandomnessAccountMismatch)?;
+        require_keys_eq!(request.key(), request_key, UniversalGameError::RandomnessAccountMismatch);
+        request.close(creator.clone())?;
+    }
+    Ok(())
+}
+
+/// Frais de transfert Token-2022 retenus sur un compte de token (0 sans
+/// l'extension de frais)
+fn withheld_transfer_fees(info: &AccountInfo) -> Result<u64> {
+    let data = info.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+    Ok(state
+        .get_extension::<TransferFeeAmount>()
+        .map(|extension| u64::from(extension.withheld_amount))
+        .unwrap_or(0))
+}
+
+// ===========================
+// CONTEXTS
+// ===========================
+
+#[derive(Accounts)]
+pub struct InitializeRegistry<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = GameRegistry::LEN,
+        seeds = [b"game_registry"],
+        bump
+    )]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterGame<'info> {
+    #[account(
+        mut,
+        seeds = [b"game_registry"],
+        bump
+    )]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetRandomnessOracle<'info> {
+    #[account(
+        mut,
+        seeds = [b"game_registry"],
+        bump
+    )]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetGameConfigBounds<'info> {
+    #[account(
+        mut,
+        seeds = [b"game_registry"],
+        bump
+    )]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    pub authority: Signer<'info>,
+}
 
 #[derive(Accounts)]
-pub struct InitializeRegistry<'info> {
+pub struct AddAllowedMint<'info> {
     #[account(
         init,
         payer = authority,
-        space = GameRegistry::LEN,
+        space = AllowedMint::LEN,
+        seeds = [b"allowed_mint", token_mint.key().as_ref()],
+        bump
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+    
+    #[account(
         seeds = [b"game_registry"],
         bump
     )]
     pub game_registry: Account<'info, GameRegistry>,
     
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
     #[account(mut)]
     pub authority: Signer<'info>,
     
@@ -931,27 +2212,88 @@ pub struct InitializeRegistry<'info> {
 }
 
 #[derive(Accounts)]
-pub struct RegisterGame<'info> {
+pub struct UpdateAllowedMint<'info> {
+    #[account(
+        mut,
+        seeds = [b"allowed_mint", allowed_mint.mint.as_ref()],
+        bump = allowed_mint.bump
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+    
+    #[account(
+        seeds = [b"game_registry"],
+        bump
+    )]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveAllowedMint<'info> {
     #[account(
         mut,
+        close = authority,
+        seeds = [b"allowed_mint", allowed_mint.mint.as_ref()],
+        bump = allowed_mint.bump
+    )]
+    pub allowed_mint: Account<'info, AllowedMint>,
+    
+    #[account(
         seeds = [b"game_registry"],
         bump
     )]
     pub game_registry: Account<'info, GameRegistry>,
     
     #[account(mut)]
-    pub creator: Signer<'info>,
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(game_type: GameType, token_mint: Option<Pubkey>, bet_amount: u64)]
+pub struct InitializeMatchQueue<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = MatchQueue::LEN,
+        seeds = [
+            b"match_queue".as_ref(),
+            &game_type.seed(),
+            token_mint.unwrap_or_default().as_ref(),
+            &bet_amount.to_le_bytes()
+        ],
+        bump
+    )]
+    pub match_queue: Account<'info, MatchQueue>,
+    
+    #[account(
+        seeds = [b"game_registry"],
+        bump
+    )]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
     
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct CreateUniversalMatch<'info> {
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = PlayerProfile::LEN,
+        seeds = [b"player_profile", creator.key().as_ref()],
+        bump
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+    
     #[account(
         init,
         payer = creator,
         space = UniversalMatch::LEN,
-        seeds = [b"match", creator.key().as_ref(), &Clock::get()?.unix_timestamp.to_le_bytes()],
+        seeds = [b"match", creator.key().as_ref(), &player_profile.match_nonce.to_le_bytes()],
         bump
     )]
     pub match_account: Account<'info, UniversalMatch>,
@@ -964,13 +2306,27 @@ pub struct CreateUniversalMatch<'info> {
     /// CHECK: Vault PDA for holding funds
     pub vault: UncheckedAccount<'info>,
     
+    #[account(
+        seeds = [b"game_registry"],
+        bump
+    )]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    /// File de matchmaking où lister le match (optionnelle)
+    #[account(mut)]
+    pub match_queue: Option<Account<'info, MatchQueue>>,
+    
     #[account(mut)]
     pub creator: Signer<'info>,
     
-    pub token_mint: Option<Account<'info, Mint>>,
-    pub creator_token_account: Option<Account<'info, TokenAccount>>,
-    pub vault_token_account: Option<Account<'info, TokenAccount>>,
-    pub token_program: Option<Program<'info, Token>>,
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    /// Entrée de l'allowlist du registre pour `token_mint` (requise en SPL)
+    pub allowed_mint: Option<Account<'info, AllowedMint>>,
+    #[account(mut)]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
@@ -989,12 +2345,19 @@ pub struct JoinMatch<'info> {
     /// CHECK: Vault PDA
     pub vault: UncheckedAccount<'info>,
     
+    /// File où le match est listé (requise si `match_account.queue` est défini)
+    #[account(mut)]
+    pub match_queue: Option<Account<'info, MatchQueue>>,
+    
     #[account(mut)]
     pub opponent: Signer<'info>,
     
-    pub opponent_token_account: Option<Account<'info, TokenAccount>>,
-    pub vault_token_account: Option<Account<'info, TokenAccount>>,
-    pub token_program: Option<Program<'info, Token>>,
+    #[account(mut)]
+    pub opponent_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     
     pub system_program: Program<'info, System>,
 }
@@ -1007,10 +2370,149 @@ pub struct SubmitMove<'info> {
     pub player: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct CommitMove<'info> {
+    #[account(mut)]
+    pub match_account: Account<'info, UniversalMatch>,
+    
+    pub player: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevealMove<'info> {
+    #[account(mut)]
+    pub match_account: Account<'info, UniversalMatch>,
+    
+    pub player: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimTimeoutForfeit<'info> {
+    #[account(mut)]
+    pub match_account: Account<'info, UniversalMatch>,
+    
+    pub claimer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimSeedForfeit<'info> {
+    #[account(mut)]
+    pub match_account: Account<'info, UniversalMatch>,
+    
+    pub claimer: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CommitSeed<'info> {
+    #[account(mut)]
+    pub match_account: Account<'info, UniversalMatch>,
+    
+    pub player: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevealSeed<'info> {
+    #[account(mut)]
+    pub match_account: Account<'info, UniversalMatch>,
+    
+    /// Requête VRF, requise (et remplie) si le match en a demandé une
+    #[account(
+        seeds = [b"randomness", match_account.key().as_ref()],
+        bump = randomness_request.bump
+    )]
+    pub randomness_request: Option<Account<'info, RandomnessRequest>>,
+    
+    pub player: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RollDice<'info> {
+    #[account(mut)]
+    pub match_account: Account<'info, UniversalMatch>,
+    
+    /// Requête VRF, requise si le match en a demandé une
+    #[account(
+        seeds = [b"randomness", match_account.key().as_ref()],
+        bump = randomness_request.bump
+    )]
+    pub randomness_request: Option<Account<'info, RandomnessRequest>>,
+    
+    pub player: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RequestRandomness<'info> {
+    #[account(mut)]
+    pub match_account: Account<'info, UniversalMatch>,
+    
+    #[account(
+        init,
+        payer = payer,
+        space = RandomnessRequest::LEN,
+        seeds = [b"randomness", match_account.key().as_ref()],
+        bump
+    )]
+    pub randomness_request: Account<'info, RandomnessRequest>,
+    
+    #[account(
+        seeds = [b"game_registry"],
+        bump
+    )]
+    pub game_registry: Account<'info, GameRegistry>,
+    
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FulfillRandomness<'info> {
+    #[account(
+        mut,
+        seeds = [b"randomness", randomness_request.match_id.as_ref()],
+        bump = randomness_request.bump
+    )]
+    pub randomness_request: Account<'info, RandomnessRequest>,
+    
+    pub oracle: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct SettleMatch<'info> {
     #[account(mut)]
     pub match_account: Account<'info, UniversalMatch>,
+    
+    /// Requête VRF, requise si le match en a demandé une (fermée au profit du
+    /// créateur avec `auto_payout`)
+    #[account(
+        mut,
+        seeds = [b"randomness", match_account.key().as_ref()],
+        bump = randomness_request.bump
+    )]
+    pub randomness_request: Option<Account<'info, RandomnessRequest>>,
+    
+    // Comptes du paiement automatique (requis avec `auto_payout`)
+    #[account(
+        mut,
+        seeds = [b"vault", match_account.key().as_ref()],
+        bump = match_account.vault_bump
+    )]
+    /// CHECK: Vault PDA
+    pub vault: Option<UncheckedAccount<'info>>,
+    
+    #[account(mut, address = match_account.creator)]
+    /// CHECK: Créateur du match, reçoit la rent du vault et du match
+    pub creator: Option<UncheckedAccount<'info>>,
+    
+    #[account(mut)]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Option<Program<'info, System>>,
 }
 
 #[derive(Accounts)]
@@ -1029,9 +2531,12 @@ pub struct ClaimWinnings<'info> {
     #[account(mut)]
     pub claimer: Signer<'info>,
     
-    pub claimer_token_account: Option<Account<'info, TokenAccount>>,
-    pub vault_token_account: Option<Account<'info, TokenAccount>>,
-    pub token_program: Option<Program<'info, Token>>,
+    #[account(mut)]
+    pub claimer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     
     pub system_program: Program<'info, System>,
 }
@@ -1049,20 +2554,72 @@ pub struct CancelMatch<'info> {
     /// CHECK: Vault PDA
     pub vault: UncheckedAccount<'info>,
     
+    /// File où le match est listé (requise si `match_account.queue` est défini)
+    #[account(mut)]
+    pub match_queue: Option<Account<'info, MatchQueue>>,
+    
     pub requester: Signer<'info>,
     
-    #[account(mut)]
+    #[account(mut, address = match_account.creator)]
     /// CHECK: Creator to refund
     pub creator: UncheckedAccount<'info>,
     
     #[account(mut)]
-    /// CHECK: Opponent to refund if exists
+    /// CHECK: Opponent to refund if exists, vérifié contre le siège 1 (sièges suivants: remaining_accounts)
     pub opponent: Option<UncheckedAccount<'info>>,
     
-    pub creator_token_account: Option<Account<'info, TokenAccount>>,
-    pub opponent_token_account: Option<Account<'info, TokenAccount>>,
-    pub vault_token_account: Option<Account<'info, TokenAccount>>,
-    pub token_program: Option<Program<'info, Token>>,
+    #[account(mut)]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub opponent_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CleanupMatch<'info> {
+    #[account(mut)]
+    pub match_account: Account<'info, UniversalMatch>,
+    
+    #[account(
+        mut,
+        seeds = [b"vault", match_account.key().as_ref()],
+        bump = match_account.vault_bump
+    )]
+    /// CHECK: Vault PDA fermé avec le match
+    pub vault: UncheckedAccount<'info>,
+    
+    /// Requête VRF, requise si le match en a demandé une (fermée au profit du créateur)
+    #[account(
+        mut,
+        seeds = [b"randomness", match_account.key().as_ref()],
+        bump = randomness_request.bump
+    )]
+    pub randomness_request: Option<Account<'info, RandomnessRequest>>,
+    
+    /// File où le match est listé (requise si `match_account.queue` est défini)
+    #[account(mut)]
+    pub match_queue: Option<Account<'info, MatchQueue>>,
+    
+    #[account(mut, address = match_account.creator)]
+    /// CHECK: Créateur du match, reçoit la rent du match et du vault
+    pub creator: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    
+    // Comptes optionnels pour SPL tokens
+    #[account(mut)]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     
     pub system_program: Program<'info, System>,
 }
@@ -1093,7 +2650,7 @@ pub struct ResolveDispute<'info> {
 pub struct CloseMatch<'info> {
     #[account(
         mut,
-        close = authority,
+        close = creator,
         constraint = match_account.status == MatchStatus::Completed
     )]
     pub match_account: Account<'info, UniversalMatch>,
@@ -1106,12 +2663,114 @@ pub struct CloseMatch<'info> {
     /// CHECK: Vault PDA que l'on ferme aussi
     pub vault: UncheckedAccount<'info>,
     
-    #[account(mut)]
+    /// Requête VRF, requise si le match en a demandé une (fermée au profit du créateur)
+    #[account(
+        mut,
+        seeds = [b"randomness", match_account.key().as_ref()],
+        bump = randomness_request.bump
+    )]
+    pub randomness_request: Option<Account<'info, RandomnessRequest>>,
+    
+    #[account(mut, address = match_account.creator)]
+    /// CHECK: Créateur du match, reçoit la rent du match et du vault
+    pub creator: UncheckedAccount<'info>,
+    
     pub authority: Signer<'info>,
     
     // Comptes optionnels pour SPL tokens
-    pub vault_token_account: Option<Account<'info, TokenAccount>>,
-    pub token_program: Option<Program<'info, Token>>,
+    #[account(mut)]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub token_mint: Option<InterfaceAccount<'info, Mint>>,
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Option<Interface<'info, TokenInterface>>,
     
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn error_code<T>(result: Result<T>) -> u32 {
+        match result {
+            Err(Error::AnchorError(error)) => error.error_code_number,
+            Err(error) => panic!("unexpected error: {:?}", error),
+            Ok(_) => panic!("expected an error"),
+        }
+    }
+    
+    fn payout(recipient: Pubkey) -> Payout {
+        Payout {
+            recipient,
+            amount: 1_000,
+            reason: PayoutReason::Winner,
+            claimed: false,
+        }
+    }
+    
+    #[test]
+    fn payout_accounts_tell_missing_accounts_from_wrong_ones() {
+        let winner = Pubkey::new_unique();
+        let stranger = Pubkey::new_unique();
+        let owner = System::id();
+        let (mut winner_lamports, mut stranger_lamports) = (0u64, 0u64);
+        let (mut winner_data, mut stranger_data) = ([0u8; 0], [0u8; 0]);
+        let winner_info = AccountInfo::new(
+            &winner, false, true, &mut winner_lamports, &mut winner_data, &owner, false, 0,
+        );
+        let stranger_info = AccountInfo::new(
+            &stranger, false, true, &mut stranger_lamports, &mut stranger_data, &owner, false, 0,
+        );
+        let payouts = [payout(winner)];
+        
+        let destinations = payout_accounts(std::slice::from_ref(&winner_info), &payouts, None).unwrap();
+        assert_eq!(destinations[0].key(), winner);
+        
+        assert_eq!(
+            error_code(payout_accounts(&[], &payouts, None)),
+            u32::from(UniversalGameError::MissingPayoutAccount)
+        );
+        assert_eq!(
+            error_code(payout_accounts(std::slice::from_ref(&stranger_info), &payouts, None)),
+            u32::from(UniversalGameError::SeatAccountMismatch)
+        );
+        assert_eq!(
+            error_code(payout_accounts(&[winner_info, stranger_info], &payouts, None)),
+            u32::from(UniversalGameError::SeatAccountMismatch)
+        );
+    }
+    
+    #[test]
+    fn recipient_account_needs_a_token_account_for_spl_matches() {
+        let player = Pubkey::new_unique();
+        let owner = System::id();
+        let token_program_id = anchor_spl::token::ID;
+        let (mut lamports, mut program_lamports) = (0u64, 0u64);
+        let (mut data, mut program_data) = ([0u8; 0], [0u8; 0]);
+        let wallet = AccountInfo::new(
+            &player, false, true, &mut lamports, &mut data, &owner, false, 0,
+        );
+        let token_program = AccountInfo::new(
+            &token_program_id, false, false, &mut program_lamports, &mut program_data, &owner, true, 0,
+        );
+        let token_accounts = VaultTokenAccounts {
+            vault_token_account: wallet.clone(),
+            mint: wallet.clone(),
+            decimals: 0,
+            token_program,
+        };
+        
+        assert_eq!(recipient_account(&wallet, &player, None).unwrap().key(), player);
+        assert_eq!(
+            error_code(recipient_account(&wallet, &Pubkey::new_unique(), None)),
+            u32::from(UniversalGameError::SeatAccountMismatch)
+        );
+        // Un wallet n'est pas un compte de token: refusé pour un match SPL
+        assert_eq!(
+            error_code(recipient_account(&wallet, &player, Some(&token_accounts))),
+            u32::from(UniversalGameError::MissingTokenAccount)
+        );
+    }
+}
@@ -1,5 +1,4 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token, TokenAccount};
 
 // ===========================
 // UNIVERSAL GAME ENGINE - 0% FEES
@@ -15,6 +14,23 @@ pub enum GameType {
     Custom(u32),        // Jeux custom avec ID
 }
 
+impl GameType {
+    /// Encodage fixe pour les seeds de PDA: [variant, id custom LE]
+    pub fn seed(&self) -> [u8; 5] {
+        let (variant, id) = match self {
+            GameType::RockPaperScissors => (0u8, 0u32),
+            GameType::Dice => (1, 0),
+            GameType::CoinFlip => (2, 0),
+            GameType::HighCard => (3, 0),
+            GameType::Custom(id) => (4, *id),
+        };
+        let mut seed = [0u8; 5];
+        seed[0] = variant;
+        seed[1..].copy_from_slice(&id.to_le_bytes());
+        seed
+    }
+}
+
 /// Structure universelle pour tous les matchs PvP
 #[account]
 pub struct UniversalMatch {
@@ -22,18 +38,25 @@ pub struct UniversalMatch {
     pub match_id: Pubkey,
     pub game_type: GameType,
     pub creator: Pubkey,
-    pub opponent: Option<Pubkey>,
+    pub seats: Vec<Seat>,             // Sièges dans l'ordre d'arrivée (0 = créateur)
     
     // === Paris (0% de frais!) ===
     pub bet_amount: u64,
     pub token_mint: Option<Pubkey>,  // SOL si None, sinon SPL token
-    pub total_pot: u64,               // bet_amount * 2 (SANS FRAIS!)
+    pub total_pot: u64,               // Somme des mises des sièges (SANS FRAIS!)
     
     // === État du jeu ===
     pub status: MatchStatus,
     pub game_state: Vec<u8>,          // Données flexibles selon le jeu
     pub round_state: Vec<u8>,         // État du gestionnaire de rounds
     pub winner: Option<Pubkey>,
+    pub outcome: Option<MatchOutcome>, // Résultat final
+    pub payouts: Vec<Payout>,         // Parts du pot fixées au règlement
+    pub current_round: u8,            // Round en cours (commence à 1)
+    
+    // === Aléatoire vérifiable ===
+    pub randomness_request: Option<Pubkey>, // Compte VRF optionnel (oracle)
+    pub seed_reveal_deadline: Option<i64>,  // Fixée quand toutes les seeds sont engagées
     
     // === Timing ===
     pub created_at: i64,
@@ -44,9 +67,31 @@ pub struct UniversalMatch {
     // === Metadata ===
     pub game_config: GameConfig,
     pub vault_bump: u8,
+    pub match_nonce: u64,             // Nonce du PlayerProfile du créateur (seeds du PDA)
+    pub bump: u8,
+    pub queue: Option<Pubkey>,        // File de matchmaking où le match est listé
+    
+    // === Match privé ===
+    pub invited_opponent: Option<Pubkey>, // Seul ce joueur peut rejoindre
+    pub join_code_hash: Option<[u8; 32]>, // sha256 du code à fournir pour rejoindre
 }
 
 impl UniversalMatch {
+    /// Siège du créateur (premier slot de game_state)
+    pub const CREATOR_SEAT: u8 = 0;
+    /// Siège de l'opposant (second slot de game_state)
+    pub const OPPONENT_SEAT: u8 = 1;
+    /// Nombre max de sièges d'une table
+    pub const MAX_SEATS: usize = 8;
+    /// Délai pour révéler sa seed une fois toutes les seeds engagées (5 minutes)
+    pub const SEED_REVEAL_WINDOW: i64 = 300;
+    /// Nombre max de paiements enregistrés au règlement (gains, side pots...)
+    pub const MAX_PAYOUTS: usize = 16;
+    /// Délai après expiration avant qu'un match jamais rejoint soit nettoyable (7 jours)
+    pub const CLEANUP_GRACE_SECONDS: i64 = 7 * 24 * 60 * 60;
+    /// Prime versée au keeper tiers sur la rent du match (plafonnée à la rent)
+    pub const CLEANUP_BOUNTY_LAMPORTS: u64 = 50_000;
+    
     pub const MAX_GAME_STATE_SIZE: usize = 256;  // Taille max pour game_state
     pub const MAX_ROUND_STATE_SIZE: usize = 128; // Taille max pour round_state
     
@@ -54,7 +99,7 @@ impl UniversalMatch {
         32 +                          // match_id
         1 + 4 +                       // game_type (enum avec variant)
         32 +                          // creator
-        1 + 32 +                      // opponent (Option)
+        4 + Self::MAX_SEATS * Seat::LEN + // seats (Vec)
         8 +                           // bet_amount
         1 + 32 +                      // token_mint (Option)
         8 +                           // total_pot
@@ -62,23 +107,77 @@ impl UniversalMatch {
         4 + Self::MAX_GAME_STATE_SIZE + // game_state (Vec)
         4 + Self::MAX_ROUND_STATE_SIZE + // round_state (Vec)
         1 + 32 +                      // winner (Option)
+        1 + MatchOutcome::MAX_LEN +   // outcome (Option)
+        4 + Self::MAX_PAYOUTS * Payout::LEN + // payouts (Vec)
+        1 +                           // current_round
+        1 + 32 +                      // randomness_request (Option)
+        1 + 8 +                       // seed_reveal_deadline (Option)
         8 +                           // created_at
         1 + 8 +                       // started_at (Option)
         1 + 8 +                       // ended_at (Option)
         8 +                           // timeout_seconds
-        32 +                          // game_config
-        1;                            // vault_bump
-        
-    /// Vérifie si le match peut être annulé (timeout)
-    pub fn can_timeout(&self) -> bool {
-        let clock = Clock::get().unwrap();
+        1 + 8 + 8 + 1 + 16 +          // game_config
+        1 +                           // vault_bump
+        8 +                           // match_nonce
+        1 +                           // bump
+        1 + 32 +                      // queue (Option)
+        1 + 32 +                      // invited_opponent (Option)
+        1 + 32;                       // join_code_hash (Option)
+    
+    /// Longueur minimale d'un code de match privé
+    pub const MIN_JOIN_CODE_LEN: usize = 8;
+    
+    /// Empreinte d'un code de match privé: sha256(match || code). Salée par
+    /// l'adresse du match, la même empreinte ne se retrouve pas d'un match à l'autre
+    pub fn hash_join_code(match_id: &Pubkey, code: &[u8]) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(match_id.as_ref());
+        hasher.update(code);
+        hasher.finalize().into()
+    }
+    
+    /// Match sur invitation ou protégé par un code
+    pub fn is_private(&self) -> bool {
+        self.invited_opponent.is_some() || self.join_code_hash.is_some()
+    }
+    
+    /// Vérifie que `player` peut rejoindre ce match privé
+    pub fn check_invite(&self, player: &Pubkey, join_code: Option<&[u8]>) -> Result<()> {
+        if let Some(invited) = self.invited_opponent {
+            require_keys_eq!(*player, invited, UniversalGameError::MatchInviteOnly);
+        }
+        if let Some(expected) = self.join_code_hash {
+            let code = join_code.ok_or(UniversalGameError::MatchInviteOnly)?;
+            require!(
+                code.len() >= Self::MIN_JOIN_CODE_LEN,
+                UniversalGameError::JoinCodeTooShort
+            );
+            require!(
+                Self::hash_join_code(&self.match_id, code) == expected,
+                UniversalGameError::MatchInviteOnly
+            );
+        }
+        Ok(())
+    }
+    
+    /// Adresse du match: PDA [b"match", créateur, nonce], dérivable hors chaîne
+    /// depuis le `match_nonce` du PlayerProfile du créateur
+    pub fn derive_address(creator: &Pubkey, match_nonce: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[b"match", creator.as_ref(), &match_nonce.to_le_bytes()],
+            &crate::ID,
+        )
+    }
+    
+    /// Vérifie si le match peut être annulé (timeout) à l'instant `now`
+    pub fn can_timeout(&self, now: i64) -> bool {
         match self.status {
             MatchStatus::WaitingForOpponent => {
-                clock.unix_timestamp > self.created_at + self.timeout_seconds
+                now > self.created_at + self.timeout_seconds
             },
             MatchStatus::InProgress => {
                 if let Some(started) = self.started_at {
-                    clock.unix_timestamp > started + self.timeout_seconds
+                    now > started + self.timeout_seconds
                 } else {
                     false
                 }
@@ -87,12 +186,361 @@ impl UniversalMatch {
         }
     }
     
+    /// Le match peut-il être nettoyé par un keeper? Annulé et remboursé,
+    /// terminé et entièrement réclamé, ou jamais rejoint et expiré depuis longtemps
+    pub fn can_cleanup(&self, now: i64) -> bool {
+        match self.status {
+            MatchStatus::Cancelled => {
+                self.seats.iter().all(|seat| seat.status == SeatStatus::Refunded)
+            },
+            MatchStatus::Completed => self.all_payouts_claimed(),
+            MatchStatus::WaitingForOpponent => {
+                self.seats.len() == 1 &&
+                now > self.created_at + self.timeout_seconds + Self::CLEANUP_GRACE_SECONDS
+            },
+            _ => false,
+        }
+    }
+    
+    /// Résout un joueur vers son siège
+    pub fn seat_of(&self, player: &Pubkey) -> Option<u8> {
+        self.seats
+            .iter()
+            .position(|seat| seat.player == *player)
+            .map(|seat| seat as u8)
+    }
+    
+    /// Joueur assis au siège donné
+    pub fn player_at(&self, seat: u8) -> Option<Pubkey> {
+        self.seats.get(seat as usize).map(|s| s.player)
+    }
+    
+    /// Premier joueur à avoir rejoint le créateur
+    pub fn opponent(&self) -> Option<Pubkey> {
+        self.player_at(Self::OPPONENT_SEAT)
+    }
+    
+    /// Toutes les places de la table sont prises
+    pub fn is_full(&self) -> bool {
+        self.seats.len() >= self.game_config.max_players as usize
+    }
+    
+    /// Assoit un joueur au prochain siège libre et ajoute sa mise au pot
+    pub fn add_seat(&mut self, player: Pubkey, stake: u64) -> Result<u8> {
+        require!(self.seat_of(&player).is_none(), UniversalGameError::AlreadySeated);
+        require!(!self.is_full(), UniversalGameError::MatchFull);
+        
+        self.seats.push(Seat::new(player, stake));
+        self.total_pot = self
+            .total_pot
+            .checked_add(stake)
+            .ok_or(UniversalGameError::InvalidGameState)?;
+        Ok(self.seats.len() as u8 - 1)
+    }
+    
+    /// Siège adverse dans un match à deux joueurs
+    pub fn other_seat(seat: u8) -> u8 {
+        if seat == Self::CREATOR_SEAT {
+            Self::OPPONENT_SEAT
+        } else {
+            Self::CREATOR_SEAT
+        }
+    }
+    
+    /// Vérifie si un siège a déjà joué pendant le round en cours
+    pub fn has_submitted(&self, seat: u8) -> bool {
+        self.seats.get(seat as usize).is_some_and(|s| s.has_moved)
+    }
+    
+    /// Vérifie si tous les sièges ont joué pendant le round en cours
+    pub fn all_moves_submitted(&self) -> bool {
+        !self.seats.is_empty() && self.seats.iter().all(|s| s.has_moved)
+    }
+    
+    /// Écrit le mouvement dans le slot dédié du siège: les `move_size` octets
+    /// à partir de `seat * move_size` dans game_state
+    pub fn record_move(&mut self, seat: u8, move_data: &[u8], move_size: usize) -> Result<()> {
+        require!(move_data.len() >= move_size, UniversalGameError::InvalidGameState);
+        self.mark_move_submitted(seat)?;
+        
+        let start = seat as usize * move_size;
+        let end = start + move_size;
+        require!(end <= Self::MAX_GAME_STATE_SIZE, UniversalGameError::InvalidGameState);
+        
+        if self.game_state.len() < end {
+            self.game_state.resize(end, 0);
+        }
+        self.game_state[start..end].copy_from_slice(&move_data[..move_size]);
+        Ok(())
+    }
+    
+    /// Marque le siège comme ayant joué ce round (un seul mouvement par round)
+    pub fn mark_move_submitted(&mut self, seat: u8) -> Result<()> {
+        let seat = self
+            .seats
+            .get_mut(seat as usize)
+            .ok_or(UniversalGameError::Unauthorized)?;
+        require!(!seat.has_moved, UniversalGameError::MoveAlreadySubmitted);
+        seat.has_moved = true;
+        Ok(())
+    }
+    
+    /// Passe au round suivant en libérant les slots de mouvement
+    pub fn advance_round(&mut self) {
+        self.current_round = self.current_round.saturating_add(1);
+        for seat in self.seats.iter_mut() {
+            seat.has_moved = false;
+        }
+    }
+    
+    /// Clôt le match sur son résultat final: gagnant, statuts des sièges,
+    /// paiements à réclamer, fin
+    pub fn complete(&mut self, outcome: MatchOutcome, payouts: Vec<Payout>, now: i64) -> Result<()> {
+        self.validate_payouts(&payouts)?;
+        
+        for (index, seat) in self.seats.iter_mut().enumerate() {
+            seat.status = outcome.seat_status(index as u8);
+        }
+        self.winner = outcome
+            .sole_winner()
+            .and_then(|seat| self.player_at(seat));
+        self.outcome = Some(outcome);
+        self.payouts = payouts;
+        self.status = MatchStatus::Completed;
+        self.ended_at = Some(now);
+        Ok(())
+    }
+    
+    /// Annule le match: l'unique transition vers Cancelled, depuis l'attente
+    /// d'un adversaire ou une partie expirée. Renvoie la raison de l'annulation.
+    /// Tant que la table n'est pas pleine, seul le créateur l'annule; les autres
+    /// sièges attendent l'expiration de l'attente
+    pub fn cancel(&mut self, requester: &Pubkey, now: i64) -> Result<&'static str> {
+        let reason = match self.status {
+            MatchStatus::WaitingForOpponent if *requester == self.creator => "no_opponent",
+            MatchStatus::WaitingForOpponent if self.can_timeout(now) => "no_opponent",
+            MatchStatus::WaitingForOpponent => return err!(UniversalGameError::Unauthorized),
+            MatchStatus::InProgress if self.can_timeout(now) => "timeout",
+            _ => return err!(UniversalGameError::CannotCancel),
+        };
+        self.status = MatchStatus::Cancelled;
+        self.ended_at = Some(now);
+        Ok(reason)
+    }
+    
+    /// Le RoundManager a déjà enregistré un round (égalités rejouées comprises)
+    pub fn has_round_history(&self) -> bool {
+        use crate::games::round_manager::RoundManager;
+        
+        !self.round_state.is_empty() &&
+            RoundManager::from_bytes(&self.round_state)
+                .map_or(true, |round_manager| !round_manager.round_history.is_empty())
+    }
+    
+    /// Tous les sièges ont engagé leur seed: l'issue d'un jeu de hasard est fixée
+    pub fn seeds_committed(&self) -> bool {
+        self.seats.iter().all(|seat| seat.entropy.has_committed())
+    }
+    
+    /// Tous les sièges ont révélé leur seed
+    pub fn seeds_revealed(&self) -> bool {
+        self.seats.iter().all(|seat| seat.entropy.seed.is_some())
+    }
+    
+    /// Un match annulé a encore des sièges à rembourser
+    pub fn has_pending_refunds(&self) -> bool {
+        self.status == MatchStatus::Cancelled &&
+            self.seats.iter().any(|seat| seat.status != SeatStatus::Refunded)
+    }
+    
+    /// Paiements par défaut d'un résultat: tout le pot au gagnant, partage
+    /// entre premiers ex aequo (le reste de la division au premier d'entre
+    /// eux), ou chaque mise rendue sur une égalité
+    pub fn outcome_payouts(&self, outcome: &MatchOutcome) -> Result<Vec<Payout>> {
+        let payouts = match outcome {
+            MatchOutcome::Winner(seat) => {
+                let winner = self.player_at(*seat).ok_or(UniversalGameError::InvalidGameState)?;
+                vec![Payout::new(winner, self.calculate_winner_amount(), PayoutReason::Winner)]
+            },
+            MatchOutcome::Ranked(ranks) => {
+                require!(ranks.len() == self.seats.len(), UniversalGameError::InvalidGameState);
+                let winners: Vec<Pubkey> = self
+                    .seats
+                    .iter()
+                    .zip(ranks)
+                    .filter(|(_, rank)| **rank == 0)
+                    .map(|(seat, _)| seat.player)
+                    .collect();
+                require!(!winners.is_empty(), UniversalGameError::InvalidGameState);
+                
+                let share = self.total_pot / winners.len() as u64;
+                let remainder = self.total_pot % winners.len() as u64;
+                let reason = if winners.len() == 1 { PayoutReason::Winner } else { PayoutReason::Tie };
+                winners
+                    .iter()
+                    .enumerate()
+                    .map(|(i, winner)| {
+                        let amount = if i == 0 { share + remainder } else { share };
+                        Payout::new(*winner, amount, reason)
+                    })
+                    .collect()
+            },
+            MatchOutcome::Draw => self
+                .seats
+                .iter()
+                .map(|seat| Payout::new(seat.player, seat.stake, PayoutReason::Tie))
+                .collect(),
+        };
+        Ok(payouts)
+    }
+    
+    /// Les paiements vont aux sièges du match et distribuent exactement le pot
+    pub fn validate_payouts(&self, payouts: &[Payout]) -> Result<()> {
+        require!(payouts.len() <= Self::MAX_PAYOUTS, UniversalGameError::InvalidPayouts);
+        
+        let mut total: u64 = 0;
+        for payout in payouts {
+            require!(
+                self.seat_of(&payout.recipient).is_some(),
+                UniversalGameError::InvalidPayouts
+            );
+            total = total
+                .checked_add(payout.amount)
+                .ok_or(UniversalGameError::InvalidPayouts)?;
+        }
+        require!(total == self.total_pot, UniversalGameError::InvalidPayouts);
+        Ok(())
+    }
+    
+    /// Marque le siège de `recipient` et ses paiements comme réclamés, et
+    /// renvoie leur total. Un siège ne réclame qu'une seule fois
+    pub fn claim_payouts(&mut self, recipient: &Pubkey) -> Result<u64> {
+        let seat = self
+            .seat_of(recipient)
+            .ok_or(UniversalGameError::Unauthorized)? as usize;
+        require!(!self.seats[seat].claimed, UniversalGameError::WinningsAlreadyClaimed);
+        self.seats[seat].claimed = true;
+        
+        let mut amount: u64 = 0;
+        for payout in self
+            .payouts
+            .iter_mut()
+            .filter(|payout| payout.recipient == *recipient && !payout.claimed)
+        {
+            amount = amount
+                .checked_add(payout.amount)
+                .ok_or(UniversalGameError::InvalidPayouts)?;
+            payout.claimed = true;
+        }
+        require!(amount > 0, UniversalGameError::NothingToClaim);
+        Ok(amount)
+    }
+    
+    /// Tous les paiements du règlement ont été réclamés
+    pub fn all_payouts_claimed(&self) -> bool {
+        self.payouts.iter().all(|payout| payout.claimed)
+    }
+    
+    /// Au moins un paiement (ou siège) a déjà été réclamé: le résultat ne
+    /// peut plus être remis en cause, le vault est déjà en partie vidé
+    pub fn any_payout_claimed(&self) -> bool {
+        self.payouts.iter().any(|payout| payout.claimed) ||
+            self.seats.iter().any(|seat| seat.claimed)
+    }
+    
+    /// Ce que le vault doit encore verser: paiements non réclamés d'un match
+    /// terminé, sinon mises des sièges non remboursés
+    pub fn outstanding_liabilities(&self) -> u64 {
+        if self.status == MatchStatus::Completed {
+            self.payouts
+                .iter()
+                .filter(|payout| !payout.claimed)
+                .map(|payout| payout.amount)
+                .sum()
+        } else {
+            self.seats
+                .iter()
+                .filter(|seat| seat.status != SeatStatus::Refunded)
+                .map(|seat| seat.stake)
+                .sum()
+        }
+    }
+    
     /// Calcule le montant pour le gagnant (100% du pot!)
     pub fn calculate_winner_amount(&self) -> u64 {
         self.total_pot  // 0% de frais = 100% au gagnant!
     }
 }
 
+/// Siège d'un match: joueur, mise déposée, statut et entropie commit-reveal.
+/// Son slot de mouvement est `game_state[seat * move_size..]`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Seat {
+    pub player: Pubkey,
+    pub stake: u64,
+    pub status: SeatStatus,
+    pub has_moved: bool,              // A joué pendant le round en cours
+    pub claimed: bool,                // A réclamé ses paiements du règlement
+    pub entropy: SeatEntropy,         // Seed commit-reveal du siège
+}
+
+impl Seat {
+    pub const LEN: usize = 32 + 8 + 1 + 1 + 1 + SeatEntropy::LEN;
+    
+    pub fn new(player: Pubkey, stake: u64) -> Self {
+        Seat {
+            player,
+            stake,
+            status: SeatStatus::Joined,
+            has_moved: false,
+            claimed: false,
+            entropy: SeatEntropy::default(),
+        }
+    }
+}
+
+/// Part du pot due à un joueur, fixée au règlement du match
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Payout {
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub reason: PayoutReason,
+    pub claimed: bool,
+}
+
+impl Payout {
+    pub const LEN: usize = 32 + 8 + 1 + 1;
+    
+    pub fn new(recipient: Pubkey, amount: u64, reason: PayoutReason) -> Self {
+        Payout {
+            recipient,
+            amount,
+            reason,
+            claimed: false,
+        }
+    }
+}
+
+/// Origine d'un paiement
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum PayoutReason {
+    Winner,
+    Tie,
+    Forfeit,
+    Penalty,
+    Refund,
+}
+
+/// Statut d'un siège
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum SeatStatus {
+    Joined,
+    Won,
+    Lost,
+    Drew,
+    Refunded,
+}
+
 /// Configuration spécifique au type de jeu
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
 pub struct GameConfig {
@@ -113,7 +561,7 @@ pub enum MatchStatus {
     Disputed,
 }
 
-/// Résultat du jeu
+/// Résultat d'un round à deux sièges (créateur contre opposant)
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
 pub enum GameResult {
     Player1Wins,
@@ -122,6 +570,187 @@ pub enum GameResult {
     Cancelled,
 }
 
+/// Résultat final d'un match, quel que soit le nombre de sièges
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub enum MatchOutcome {
+    /// Le siège remporte tout le pot
+    Winner(u8),
+    /// Rang de chaque siège, indexé par siège (0 = premier, ex aequo possibles);
+    /// les sièges au premier rang se partagent le pot
+    Ranked(Vec<u8>),
+    /// Aucun gagnant: chaque siège récupère sa mise
+    Draw,
+}
+
+impl MatchOutcome {
+    pub const MAX_LEN: usize = 1 + 4 + UniversalMatch::MAX_SEATS;
+    
+    /// Résultat d'un match à deux sièges
+    pub fn from_result(result: GameResult) -> Self {
+        match result {
+            GameResult::Player1Wins => MatchOutcome::Winner(UniversalMatch::CREATOR_SEAT),
+            GameResult::Player2Wins => MatchOutcome::Winner(UniversalMatch::OPPONENT_SEAT),
+            GameResult::Draw | GameResult::Cancelled => MatchOutcome::Draw,
+        }
+    }
+    
+    /// Classement à partir d'un score par siège (le plus haut est premier)
+    pub fn from_scores(scores: &[u16]) -> Self {
+        let ranks: Vec<u8> = scores
+            .iter()
+            .map(|score| scores.iter().filter(|other| *other > score).count() as u8)
+            .collect();
+        
+        if ranks.iter().all(|rank| *rank == 0) {
+            MatchOutcome::Draw
+        } else {
+            MatchOutcome::Ranked(ranks)
+        }
+    }
+    
+    /// Siège gagnant s'il est seul au premier rang
+    pub fn sole_winner(&self) -> Option<u8> {
+        match self {
+            MatchOutcome::Winner(seat) => Some(*seat),
+            MatchOutcome::Ranked(ranks) => {
+                let mut winners = (0..ranks.len() as u8).filter(|s| ranks[*s as usize] == 0);
+                match (winners.next(), winners.next()) {
+                    (Some(seat), None) => Some(seat),
+                    _ => None,
+                }
+            },
+            MatchOutcome::Draw => None,
+        }
+    }
+    
+    /// Statut final d'un siège
+    pub fn seat_status(&self, seat: u8) -> SeatStatus {
+        match self {
+            MatchOutcome::Winner(winner) => {
+                if *winner == seat { SeatStatus::Won } else { SeatStatus::Lost }
+            },
+            MatchOutcome::Ranked(ranks) => match ranks.get(seat as usize) {
+                Some(0) if self.sole_winner().is_some() => SeatStatus::Won,
+                Some(0) => SeatStatus::Drew,
+                _ => SeatStatus::Lost,
+            },
+            MatchOutcome::Draw => SeatStatus::Drew,
+        }
+    }
+}
+
+// ===========================
+// PLAYER PROFILE
+// ===========================
+
+/// Profil d'un joueur: fournit le nonce des seeds de ses matchs, pour que
+/// chaque création ait une adresse unique et prévisible
+#[account]
+pub struct PlayerProfile {
+    pub player: Pubkey,
+    pub match_nonce: u64,   // Nonce du prochain match créé (= matchs déjà créés)
+    pub bump: u8,
+}
+
+impl PlayerProfile {
+    pub const LEN: usize = 8 + // Discriminator
+        32 +                    // player
+        8 +                     // match_nonce
+        1;                      // bump
+    
+    pub fn derive_address(player: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"player_profile", player.as_ref()], &crate::ID)
+    }
+    
+    /// Adresse du prochain match que créera ce joueur
+    pub fn next_match_address(&self) -> (Pubkey, u8) {
+        UniversalMatch::derive_address(&self.player, self.match_nonce)
+    }
+}
+
+// ===========================
+// MATCHMAKING QUEUE
+// ===========================
+
+/// File de matchs ouverts pour un (GameType, mint, mise): `quick_join`
+/// rejoint le plus ancien sans scanner les comptes
+#[account]
+pub struct MatchQueue {
+    pub game_type: GameType,
+    pub token_mint: Option<Pubkey>,  // SOL si None
+    pub bet_amount: u64,             // Palier de mise: mise exacte des matchs listés
+    pub entries: Vec<QueueEntry>,    // Du plus ancien au plus récent
+    pub bump: u8,
+}
+
+/// Match en attente dans une file
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct QueueEntry {
+    pub match_id: Pubkey,
+    pub creator: Pubkey,
+    pub created_at: i64,
+    pub expires_at: i64,
+}
+
+impl QueueEntry {
+    pub const LEN: usize = 32 + 32 + 8 + 8;
+}
+
+impl MatchQueue {
+    pub const MAX_ENTRIES: usize = 32;
+    
+    pub const LEN: usize = 8 +       // Discriminator
+        1 + 4 +                       // game_type
+        1 + 32 +                      // token_mint (Option)
+        8 +                           // bet_amount
+        4 + Self::MAX_ENTRIES * QueueEntry::LEN + // entries
+        1;                            // bump
+    
+    pub fn derive_address(game_type: GameType, token_mint: Option<Pubkey>, bet_amount: u64) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                b"match_queue",
+                &game_type.seed(),
+                token_mint.unwrap_or_default().as_ref(),
+                &bet_amount.to_le_bytes(),
+            ],
+            &crate::ID,
+        )
+    }
+    
+    /// Le match correspond-il au palier de cette file?
+    pub fn accepts(&self, match_data: &UniversalMatch) -> bool {
+        self.game_type == match_data.game_type
+            && self.token_mint == match_data.token_mint
+            && self.bet_amount == match_data.bet_amount
+    }
+    
+    /// Retire les matchs expirés (ils ne peuvent plus qu'être annulés)
+    pub fn prune_expired(&mut self, now: i64) {
+        self.entries.retain(|entry| entry.expires_at >= now);
+    }
+    
+    pub fn push(&mut self, entry: QueueEntry) -> Result<()> {
+        require!(
+            self.entries.len() < Self::MAX_ENTRIES,
+            UniversalGameError::QueueFull
+        );
+        self.entries.push(entry);
+        Ok(())
+    }
+    
+    /// Plus ancien match encore ouvert que `player` peut rejoindre
+    pub fn oldest_for(&self, player: &Pubkey, now: i64) -> Option<&QueueEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.creator != *player && entry.expires_at >= now)
+    }
+    
+    pub fn remove(&mut self, match_id: &Pubkey) {
+        self.entries.retain(|entry| entry.match_id != *match_id);
+    }
+}
+
 // ===========================
 // GAME REGISTRY SYSTEM
 // ===========================
@@ -133,6 +762,7 @@ pub struct GameRegistry {
     pub total_games: u32,
     pub active_games: Vec<GameDefinition>,
     pub paused: bool,
+    pub randomness_oracle: Option<Pubkey>, // Oracle autorisé à remplir les requêtes VRF
 }
 
 impl GameRegistry {
@@ -142,7 +772,19 @@ impl GameRegistry {
         32 +                            // authority
         4 +                             // total_games
         4 + (Self::MAX_GAMES * GameDefinition::LEN) + // active_games
-        1;                              // paused
+        1 +                             // paused
+        1 + 32;                         // randomness_oracle (Option)
+    
+    /// Définition active d'un type de jeu
+    pub fn find_game(&self, game_type: GameType) -> Result<&GameDefinition> {
+        let game_def = self
+            .active_games
+            .iter()
+            .find(|game| game.game_type == game_type)
+            .ok_or(UniversalGameError::GameNotRegistered)?;
+        require!(game_def.is_active, UniversalGameError::GameNotActive);
+        Ok(game_def)
+    }
 }
 
 /// Définition d'un jeu dans le registre
@@ -156,6 +798,7 @@ pub struct GameDefinition {
     pub total_matches: u64,
     pub is_active: bool,
     pub game_token: Option<Pubkey>,  // Token optionnel du jeu
+    pub config_bounds: GameConfigBounds, // Limites de table imposées par l'opérateur
 }
 
 impl GameDefinition {
@@ -167,7 +810,122 @@ impl GameDefinition {
         8 +                    // created_at
         8 +                    // total_matches
         1 +                    // is_active
-        1 + 32;                // game_token (Option)
+        1 + 32 +               // game_token (Option)
+        GameConfigBounds::LEN; // config_bounds
+}
+
+/// Bornes canoniques d'un GameConfig pour un jeu du registre
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct GameConfigBounds {
+    pub min_players: u8,
+    pub max_players: u8,
+    pub min_rounds: u8,
+    pub max_rounds: u8,
+    pub min_bet: u64,
+    pub max_bet: u64,
+    /// Valeur max autorisée pour chaque custom_param (0 = doit rester à 0)
+    pub max_custom_params: [u8; 16],
+}
+
+impl GameConfigBounds {
+    pub const LEN: usize = 1 + 1 + 1 + 1 + 8 + 8 + 16;
+    
+    /// Vérifie que les bornes elles-mêmes sont cohérentes. Si le jeu ne gère
+    /// pas ses rounds, l'historique du RoundManager doit tenir dans round_state
+    pub fn validate(&self, manages_rounds: bool) -> Result<()> {
+        use crate::games::round_manager::RoundManager;
+        
+        require!(
+            self.min_players >= 2 &&
+            self.min_players <= self.max_players &&
+            self.max_players as usize <= UniversalMatch::MAX_SEATS,
+            UniversalGameError::InvalidConfigBounds
+        );
+        require!(
+            self.min_rounds >= 1 && self.min_rounds <= self.max_rounds,
+            UniversalGameError::InvalidConfigBounds
+        );
+        require!(
+            manages_rounds ||
+            RoundManager::max_len(self.max_rounds) <= UniversalMatch::MAX_ROUND_STATE_SIZE,
+            UniversalGameError::InvalidConfigBounds
+        );
+        require!(
+            self.min_bet > 0 && self.min_bet <= self.max_bet,
+            UniversalGameError::InvalidConfigBounds
+        );
+        Ok(())
+    }
+    
+    /// Vérifie qu'une config demandée reste dans les limites de la table
+    pub fn check(&self, config: &GameConfig) -> Result<()> {
+        require!(
+            config.max_players >= self.min_players && config.max_players <= self.max_players,
+            UniversalGameError::ConfigOutOfBounds
+        );
+        require!(
+            config.rounds >= self.min_rounds && config.rounds <= self.max_rounds,
+            UniversalGameError::ConfigOutOfBounds
+        );
+        require!(
+            config.min_bet >= self.min_bet
+                && config.max_bet <= self.max_bet
+                && config.min_bet <= config.max_bet,
+            UniversalGameError::ConfigOutOfBounds
+        );
+        require!(
+            config
+                .custom_params
+                .iter()
+                .zip(self.max_custom_params.iter())
+                .all(|(value, max)| value <= max),
+            UniversalGameError::ConfigOutOfBounds
+        );
+        Ok(())
+    }
+}
+
+// ===========================
+// MINTS AUTORISÉS
+// ===========================
+
+/// Mint SPL accepté pour les mises, avec ses limites, géré par l'autorité du
+/// registre. PDA [b"allowed_mint", mint]: sans ce compte, pas de match en tokens
+#[account]
+pub struct AllowedMint {
+    pub mint: Pubkey,
+    pub decimals: u8,
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub enabled: bool,
+    pub bump: u8,
+}
+
+impl AllowedMint {
+    pub const LEN: usize = 8 +  // Discriminator
+        32 +                     // mint
+        1 +                      // decimals
+        8 +                      // min_bet
+        8 +                      // max_bet
+        1 +                      // enabled
+        1;                       // bump
+    
+    /// Limites de mise cohérentes
+    pub fn validate_limits(min_bet: u64, max_bet: u64) -> Result<()> {
+        require!(
+            min_bet > 0 && min_bet <= max_bet,
+            UniversalGameError::InvalidConfigBounds
+        );
+        Ok(())
+    }
+    
+    /// Vérifie qu'une mise dans ce mint est acceptée
+    pub fn check_bet(&self, bet_amount: u64) -> Result<()> {
+        require!(self.enabled, UniversalGameError::MintNotAllowed);
+        require!(bet_amount >= self.min_bet, UniversalGameError::InsufficientBet);
+        require!(bet_amount <= self.max_bet, UniversalGameError::BetTooLarge);
+        Ok(())
+    }
 }
 
 // ===========================
@@ -207,6 +965,138 @@ pub enum DisputeResolution {
     Player2Wins,
     Draw,
     Cancel,
+    SeatWins(u8),
+}
+
+// ===========================
+// RANDOMNESS (COMMIT-REVEAL + VRF)
+// ===========================
+
+use sha2::{Digest, Sha256};
+
+/// Source d'aléatoire pour tous les jeux de hasard.
+/// Le résultat ne doit dépendre ni du slot ni de l'horloge, que le settler choisit.
+pub trait RandomnessSource {
+    /// 32 octets aléatoires, séparés par domaine (ex: b"coin_flip", numéro de round)
+    fn random_bytes(&self, domain: &[u8]) -> Result<[u8; 32]>;
+    
+    /// Entier dans [0, bound) dérivé de `random_bytes`
+    fn random_below(&self, domain: &[u8], bound: u64) -> Result<u64> {
+        require!(bound > 0, UniversalGameError::InvalidGameState);
+        let bytes = self.random_bytes(domain)?;
+        let value = u64::from_le_bytes(bytes[0..8].try_into().unwrap());
+        Ok(value % bound)
+    }
+}
+
+/// Entropie commit-reveal fournie par un siège
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct SeatEntropy {
+    pub commitment: [u8; 32],
+    pub seed: Option<[u8; 32]>,
+}
+
+impl SeatEntropy {
+    pub const LEN: usize = 32 + 1 + 32;
+    
+    /// Engagement attendu pour une seed: sha256(seed)
+    pub fn commitment_for(seed: &[u8; 32]) -> [u8; 32] {
+        Sha256::digest(seed).into()
+    }
+    
+    pub fn has_committed(&self) -> bool {
+        self.commitment != [0u8; 32]
+    }
+}
+
+/// Requête d'aléatoire de type oracle/VRF, remplie par l'oracle du registre
+/// (sur localnet, une keypair de test joue ce rôle)
+#[account]
+pub struct RandomnessRequest {
+    pub match_id: Pubkey,
+    pub oracle: Pubkey,
+    pub randomness: Option<[u8; 32]>,
+    pub requested_at: i64,
+    pub fulfilled_at: Option<i64>,
+    pub bump: u8,
+}
+
+impl RandomnessRequest {
+    pub const LEN: usize = 8 +  // Discriminator
+        32 +                     // match_id
+        32 +                     // oracle
+        1 + 32 +                 // randomness (Option)
+        8 +                      // requested_at
+        1 + 8 +                  // fulfilled_at (Option)
+        1;                       // bump
+}
+
+/// Aléatoire d'un match: seeds révélées de chaque siège + sortie VRF optionnelle.
+/// Aucun joueur ne connaît le résultat avant que tous aient révélé.
+pub struct MatchRandomness {
+    match_id: Pubkey,
+    seeds: Vec<[u8; 32]>,
+    vrf_output: Option<[u8; 32]>,
+}
+
+impl MatchRandomness {
+    /// Construit la source depuis le match; exige toutes les seeds révélées et,
+    /// si le match a demandé une requête VRF, qu'elle soit fournie et remplie
+    pub fn from_match(
+        match_data: &UniversalMatch,
+        request: Option<&RandomnessRequest>,
+    ) -> Result<Self> {
+        let seeds = match_data.seats
+            .iter()
+            .map(|s| s.entropy.seed.ok_or(error!(UniversalGameError::RandomnessNotReady)))
+            .collect::<Result<Vec<_>>>()?;
+        
+        let vrf_output = match match_data.randomness_request {
+            Some(_) => {
+                let request = request.ok_or(UniversalGameError::RandomnessAccountMismatch)?;
+                require!(
+                    request.match_id == match_data.match_id,
+                    UniversalGameError::RandomnessAccountMismatch
+                );
+                Some(request.randomness.ok_or(UniversalGameError::RandomnessNotReady)?)
+            },
+            None => None,
+        };
+        
+        Ok(MatchRandomness {
+            match_id: match_data.match_id,
+            seeds,
+            vrf_output,
+        })
+    }
+}
+
+impl RandomnessSource for MatchRandomness {
+    fn random_bytes(&self, domain: &[u8]) -> Result<[u8; 32]> {
+        let mut hasher = Sha256::new();
+        hasher.update(b"universal_pvp/randomness");
+        hasher.update(self.match_id.as_ref());
+        for seed in &self.seeds {
+            hasher.update(seed);
+        }
+        if let Some(vrf_output) = &self.vrf_output {
+            hasher.update(vrf_output);
+        }
+        hasher.update(domain);
+        Ok(hasher.finalize().into())
+    }
+}
+
+// ===========================
+// TIMEOUTS COMMIT-REVEAL
+// ===========================
+
+/// Joueur(s) fautif(s) quand la deadline de reveal est dépassée
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum TimeoutType {
+    CreatorNoReveal,
+    OpponentNoReveal,
+    SeedNoReveal,   // Au moins un siège n'a pas révélé sa seed à temps
 }
 
 // ===========================
@@ -226,6 +1116,34 @@ pub struct MatchCreated {
 pub struct MatchJoined {
     pub match_id: Pubkey,
     pub opponent: Pubkey,
+    pub seat: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MatchStarted {
+    pub match_id: Pubkey,
+    pub players: Vec<Pubkey>,
+    pub total_pot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MoveSubmitted {
+    pub match_id: Pubkey,
+    pub player: Pubkey,
+    pub seat: u8,
+    pub round: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MoveRevealed {
+    pub match_id: Pubkey,
+    pub player: Pubkey,
+    pub seat: u8,
+    pub round: u8,
+    pub move_data: Vec<u8>,
     pub timestamp: i64,
 }
 
@@ -234,7 +1152,8 @@ pub struct MatchCompleted {
     pub match_id: Pubkey,
     pub winner: Option<Pubkey>,
     pub total_pot: u64,
-    pub game_result: GameResult,
+    pub outcome: MatchOutcome,
+    pub payouts: Vec<Payout>,
     pub timestamp: i64,
 }
 
@@ -262,6 +1181,45 @@ pub struct MatchCancelled {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct MatchCleanedUp {
+    pub match_id: Pubkey,
+    pub keeper: Pubkey,
+    pub bounty: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MatchTimedOut {
+    pub match_id: Pubkey,
+    pub timeout_type: TimeoutType,
+    pub winner: Option<Pubkey>,
+    pub total_pot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SeedRevealed {
+    pub match_id: Pubkey,
+    pub player: Pubkey,
+    pub seat: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DiceRolled {
+    pub match_id: Pubkey,
+    pub dice: Vec<Vec<u8>>,           // Dés de chaque siège, dans l'ordre des sièges
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RandomnessFulfilled {
+    pub match_id: Pubkey,
+    pub oracle: Pubkey,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct MatchDisputed {
     pub match_id: Pubkey,
@@ -331,4 +1289,463 @@ pub enum UniversalGameError {
     
     #[msg("Custom game error")]
     CustomError,
-}
\ No newline at end of file
+    
+    #[msg("Move already submitted for this round")]
+    MoveAlreadySubmitted,
+    
+    #[msg("Both players must submit their move first")]
+    MovesNotSubmitted,
+    
+    #[msg("Match already under way: settle it or claim a forfeit instead")]
+    ForfeitRequired,
+    
+    #[msg("No forfeit to claim: one player must have revealed after both committed, and the reveal window passed")]
+    NoForfeitToClaim,
+    
+    #[msg("Both players revealed: settle the match instead")]
+    ShouldSettleNotTimeout,
+    
+    #[msg("Randomness not ready: seeds or VRF output missing")]
+    RandomnessNotReady,
+    
+    #[msg("Seed already committed")]
+    SeedAlreadyCommitted,
+    
+    #[msg("Seed does not match commitment")]
+    InvalidSeedReveal,
+    
+    #[msg("Randomness oracle not configured")]
+    RandomnessOracleNotSet,
+    
+    #[msg("Randomness request does not belong to this match")]
+    RandomnessAccountMismatch,
+    
+    #[msg("Randomness already requested or seeds already revealed")]
+    RandomnessAlreadyRequested,
+    
+    #[msg("Randomness already fulfilled")]
+    RandomnessAlreadyFulfilled,
+    
+    #[msg("Seed reveal deadline has passed")]
+    SeedRevealDeadlinePassed,
+    
+    #[msg("Seed reveal deadline has not passed yet")]
+    SeedRevealDeadlineNotPassed,
+    
+    #[msg("This game uses commit_move and reveal_move")]
+    CommitRevealRequired,
+    
+    #[msg("Player cannot make a move right now")]
+    MoveNotAllowed,
+    
+    #[msg("Game type is not registered")]
+    GameNotRegistered,
+    
+    #[msg("Invalid game config bounds")]
+    InvalidConfigBounds,
+    
+    #[msg("Game config is outside the registered bounds")]
+    ConfigOutOfBounds,
+    
+    #[msg("This game type is already registered")]
+    GameAlreadyRegistered,
+    
+    #[msg("Match queue is full")]
+    QueueFull,
+    
+    #[msg("Match queue does not match this match")]
+    QueueMismatch,
+    
+    #[msg("Match queue account required: the match is listed in a queue")]
+    QueueAccountRequired,
+    
+    #[msg("No open match available in this queue")]
+    NoMatchAvailable,
+    
+    #[msg("This match is invite-only")]
+    MatchInviteOnly,
+    
+    #[msg("Join code must be at least 8 bytes long")]
+    JoinCodeTooShort,
+    
+    #[msg("Player already has a seat in this match")]
+    AlreadySeated,
+    
+    #[msg("All seats of this match are taken")]
+    MatchFull,
+    
+    #[msg("Account missing or does not match the seated player")]
+    SeatAccountMismatch,
+    
+    #[msg("Auto payout needs the vault, creator, system program and one account per payout")]
+    MissingPayoutAccount,
+    
+    #[msg("Payouts must go to seated players and add up to the pot")]
+    InvalidPayouts,
+    
+    #[msg("No payout left to claim for this player")]
+    NothingToClaim,
+    
+    #[msg("Winnings already claimed")]
+    WinningsAlreadyClaimed,
+    
+    #[msg("Some payouts have not been claimed yet")]
+    UnclaimedPayouts,
+    
+    #[msg("Match cannot be cleaned up yet")]
+    CleanupNotAllowed,
+    
+    #[msg("Token account or token program missing for an SPL match")]
+    MissingTokenAccount,
+    
+    #[msg("Token account mint does not match the match mint")]
+    MintMismatch,
+    
+    #[msg("Token account is not owned by the expected wallet or vault")]
+    TokenOwnerMismatch,
+    
+    #[msg("Token mint is not allowed for wagers")]
+    MintNotAllowed,
+    
+    #[msg("Match already cancelled and refunded")]
+    AlreadyRefunded,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn error_code<T: std::fmt::Debug>(result: Result<T>) -> u32 {
+        match result.unwrap_err() {
+            Error::AnchorError(error) => error.error_code_number,
+            error => panic!("unexpected error: {:?}", error),
+        }
+    }
+    
+    fn match_with_seats(stakes: &[u64]) -> UniversalMatch {
+        let creator = Pubkey::new_unique();
+        let mut seats = vec![Seat::new(creator, stakes[0])];
+        seats.extend(stakes[1..].iter().map(|stake| Seat::new(Pubkey::new_unique(), *stake)));
+        
+        UniversalMatch {
+            match_id: Pubkey::new_unique(),
+            game_type: GameType::CoinFlip,
+            creator,
+            seats,
+            bet_amount: stakes[0],
+            token_mint: None,
+            total_pot: stakes.iter().sum(),
+            status: MatchStatus::InProgress,
+            game_state: Vec::new(),
+            round_state: Vec::new(),
+            winner: None,
+            outcome: None,
+            payouts: Vec::new(),
+            current_round: 1,
+            randomness_request: None,
+            seed_reveal_deadline: None,
+            created_at: 0,
+            started_at: Some(0),
+            ended_at: None,
+            timeout_seconds: 3600,
+            game_config: GameConfig {
+                max_players: stakes.len() as u8,
+                ..GameConfig::default()
+            },
+            vault_bump: 255,
+            match_nonce: 0,
+            bump: 255,
+            queue: None,
+            invited_opponent: None,
+            join_code_hash: None,
+        }
+    }
+    
+    #[test]
+    fn outcome_payouts_distribute_the_whole_pot() {
+        let table = match_with_seats(&[100, 100, 100]);
+        
+        let winner = table.outcome_payouts(&MatchOutcome::Winner(1)).unwrap();
+        assert_eq!(winner, vec![Payout::new(table.seats[1].player, 300, PayoutReason::Winner)]);
+        
+        // Deux premiers ex aequo: le reste de la division va au premier
+        let mut uneven = match_with_seats(&[100, 100, 101]);
+        uneven.total_pot = 301;
+        let tie = uneven.outcome_payouts(&MatchOutcome::Ranked(vec![0, 1, 0])).unwrap();
+        assert_eq!(tie, vec![
+            Payout::new(uneven.seats[0].player, 151, PayoutReason::Tie),
+            Payout::new(uneven.seats[2].player, 150, PayoutReason::Tie),
+        ]);
+        
+        let draw = table.outcome_payouts(&MatchOutcome::Draw).unwrap();
+        assert_eq!(draw.len(), 3);
+        assert!(draw.iter().all(|payout| payout.amount == 100 && payout.reason == PayoutReason::Tie));
+        
+        for payouts in [winner, draw] {
+            table.validate_payouts(&payouts).unwrap();
+        }
+        uneven.validate_payouts(&tie).unwrap();
+    }
+    
+    #[test]
+    fn outcome_payouts_reject_inconsistent_ranks() {
+        let table = match_with_seats(&[100, 100]);
+        
+        assert_eq!(
+            error_code(table.outcome_payouts(&MatchOutcome::Ranked(vec![0]))),
+            u32::from(UniversalGameError::InvalidGameState)
+        );
+        assert_eq!(
+            error_code(table.outcome_payouts(&MatchOutcome::Ranked(vec![1, 1]))),
+            u32::from(UniversalGameError::InvalidGameState)
+        );
+        assert_eq!(
+            error_code(table.outcome_payouts(&MatchOutcome::Winner(2))),
+            u32::from(UniversalGameError::InvalidGameState)
+        );
+    }
+    
+    #[test]
+    fn validate_payouts_rejects_strangers_and_wrong_totals() {
+        let table = match_with_seats(&[100, 100]);
+        let creator = table.seats[0].player;
+        
+        let stranger = vec![Payout::new(Pubkey::new_unique(), 200, PayoutReason::Winner)];
+        assert_eq!(
+            error_code(table.validate_payouts(&stranger)),
+            u32::from(UniversalGameError::InvalidPayouts)
+        );
+        
+        let short = vec![Payout::new(creator, 199, PayoutReason::Winner)];
+        assert_eq!(
+            error_code(table.validate_payouts(&short)),
+            u32::from(UniversalGameError::InvalidPayouts)
+        );
+        
+        let overflow = vec![
+            Payout::new(creator, u64::MAX, PayoutReason::Winner),
+            Payout::new(creator, 1, PayoutReason::Penalty),
+        ];
+        assert_eq!(
+            error_code(table.validate_payouts(&overflow)),
+            u32::from(UniversalGameError::InvalidPayouts)
+        );
+        
+        let too_many = vec![Payout::new(creator, 0, PayoutReason::Tie); UniversalMatch::MAX_PAYOUTS + 1];
+        assert_eq!(
+            error_code(table.validate_payouts(&too_many)),
+            u32::from(UniversalGameError::InvalidPayouts)
+        );
+    }
+    
+    #[test]
+    fn claim_payouts_pays_each_seat_once() {
+        let mut table = match_with_seats(&[100, 100]);
+        let creator = table.seats[0].player;
+        let opponent = table.seats[1].player;
+        let payouts = table.outcome_payouts(&MatchOutcome::Winner(0)).unwrap();
+        table.complete(MatchOutcome::Winner(0), payouts, 10).unwrap();
+        
+        assert_eq!(table.winner, Some(creator));
+        assert_eq!(table.seats[0].status, SeatStatus::Won);
+        assert_eq!(table.seats[1].status, SeatStatus::Lost);
+        assert_eq!(table.outstanding_liabilities(), 200);
+        
+        assert_eq!(table.claim_payouts(&creator).unwrap(), 200);
+        assert!(table.all_payouts_claimed());
+        assert!(table.any_payout_claimed());
+        assert_eq!(table.outstanding_liabilities(), 0);
+        
+        assert_eq!(
+            error_code(table.claim_payouts(&creator)),
+            u32::from(UniversalGameError::WinningsAlreadyClaimed)
+        );
+        assert_eq!(
+            error_code(table.claim_payouts(&opponent)),
+            u32::from(UniversalGameError::NothingToClaim)
+        );
+        assert_eq!(
+            error_code(table.claim_payouts(&Pubkey::new_unique())),
+            u32::from(UniversalGameError::Unauthorized)
+        );
+    }
+    
+    #[test]
+    fn outstanding_liabilities_follow_unclaimed_payouts_and_refunds() {
+        let mut table = match_with_seats(&[100, 70, 30]);
+        assert_eq!(table.outstanding_liabilities(), 200);
+        
+        table.seats[1].status = SeatStatus::Refunded;
+        assert_eq!(table.outstanding_liabilities(), 130);
+        table.seats[1].status = SeatStatus::Joined;
+        
+        let payouts = table.outcome_payouts(&MatchOutcome::Draw).unwrap();
+        table.complete(MatchOutcome::Draw, payouts, 10).unwrap();
+        assert_eq!(table.outstanding_liabilities(), 200);
+        
+        let second = table.seats[1].player;
+        assert_eq!(table.claim_payouts(&second).unwrap(), 70);
+        assert_eq!(table.outstanding_liabilities(), 130);
+        assert!(!table.all_payouts_claimed());
+    }
+    
+    #[test]
+    fn can_cleanup_waits_for_refunds_claims_or_the_grace_period() {
+        let mut table = match_with_seats(&[100, 100]);
+        assert!(!table.can_cleanup(i64::MAX));
+        
+        let payouts = table.outcome_payouts(&MatchOutcome::Winner(0)).unwrap();
+        table.complete(MatchOutcome::Winner(0), payouts, 10).unwrap();
+        assert!(!table.can_cleanup(10));
+        let creator = table.seats[0].player;
+        table.claim_payouts(&creator).unwrap();
+        assert!(table.can_cleanup(10));
+        
+        // Table jamais rejointe: nettoyable une fois l'attente et la grâce écoulées
+        let mut open = match_with_seats(&[100]);
+        open.status = MatchStatus::WaitingForOpponent;
+        let expiry = open.created_at + open.timeout_seconds + UniversalMatch::CLEANUP_GRACE_SECONDS;
+        assert!(!open.can_cleanup(expiry));
+        assert!(open.can_cleanup(expiry + 1));
+    }
+    
+    #[test]
+    fn allowed_mint_enforces_its_bet_limits() {
+        let mut allowed = AllowedMint {
+            mint: Pubkey::new_unique(),
+            decimals: 6,
+            min_bet: 1_000,
+            max_bet: 5_000,
+            enabled: true,
+            bump: 255,
+        };
+        
+        allowed.check_bet(1_000).unwrap();
+        allowed.check_bet(5_000).unwrap();
+        assert_eq!(error_code(allowed.check_bet(999)), u32::from(UniversalGameError::InsufficientBet));
+        assert_eq!(error_code(allowed.check_bet(5_001)), u32::from(UniversalGameError::BetTooLarge));
+        
+        allowed.enabled = false;
+        assert_eq!(error_code(allowed.check_bet(2_000)), u32::from(UniversalGameError::MintNotAllowed));
+        
+        assert_eq!(
+            error_code(AllowedMint::validate_limits(0, 5_000)),
+            u32::from(UniversalGameError::InvalidConfigBounds)
+        );
+        assert_eq!(
+            error_code(AllowedMint::validate_limits(5_001, 5_000)),
+            u32::from(UniversalGameError::InvalidConfigBounds)
+        );
+    }
+    
+    #[test]
+    fn join_code_hash_is_salted_by_the_match() {
+        let mut first = match_with_seats(&[100, 100]);
+        let second = match_with_seats(&[100, 100]);
+        let code = b"solduel-42";
+        
+        assert_ne!(
+            UniversalMatch::hash_join_code(&first.match_id, code),
+            UniversalMatch::hash_join_code(&second.match_id, code)
+        );
+        
+        first.join_code_hash = Some(UniversalMatch::hash_join_code(&first.match_id, code));
+        let player = Pubkey::new_unique();
+        first.check_invite(&player, Some(code)).unwrap();
+        assert_eq!(
+            error_code(first.check_invite(&player, Some(b"solduel-41"))),
+            u32::from(UniversalGameError::MatchInviteOnly)
+        );
+        assert_eq!(
+            error_code(first.check_invite(&player, Some(b"short"))),
+            u32::from(UniversalGameError::JoinCodeTooShort)
+        );
+        assert_eq!(
+            error_code(first.check_invite(&player, None)),
+            u32::from(UniversalGameError::MatchInviteOnly)
+        );
+    }
+    
+    #[test]
+    fn cancel_happens_once_and_tracks_refunds() {
+        let mut table = match_with_seats(&[100, 100]);
+        table.status = MatchStatus::WaitingForOpponent;
+        table.started_at = None;
+        let creator = table.creator;
+        
+        assert!(!table.has_pending_refunds());
+        assert_eq!(table.cancel(&creator, 20).unwrap(), "no_opponent");
+        assert_eq!(table.status, MatchStatus::Cancelled);
+        assert_eq!(table.ended_at, Some(20));
+        assert!(table.has_pending_refunds());
+        
+        table.seats[0].status = SeatStatus::Refunded;
+        assert!(table.has_pending_refunds());
+        table.seats[1].status = SeatStatus::Refunded;
+        assert!(!table.has_pending_refunds());
+        assert_eq!(table.outstanding_liabilities(), 0);
+        assert!(table.can_cleanup(20));
+        
+        assert_eq!(
+            error_code(table.cancel(&creator, 30)),
+            u32::from(UniversalGameError::CannotCancel)
+        );
+    }
+    
+    #[test]
+    fn completed_match_cannot_be_cancelled() {
+        let mut table = match_with_seats(&[100, 100]);
+        let payouts = table.outcome_payouts(&MatchOutcome::Winner(1)).unwrap();
+        table.complete(MatchOutcome::Winner(1), payouts, 10).unwrap();
+        let creator = table.creator;
+        
+        assert_eq!(
+            error_code(table.cancel(&creator, 20)),
+            u32::from(UniversalGameError::CannotCancel)
+        );
+        assert!(!table.has_pending_refunds());
+    }
+    
+    #[test]
+    fn cancel_uses_the_given_time_for_timeouts() {
+        let mut waiting = match_with_seats(&[100, 100]);
+        waiting.status = MatchStatus::WaitingForOpponent;
+        waiting.started_at = None;
+        let opponent = waiting.seats[1].player;
+        
+        // Attente non expirée: seul le créateur annule
+        assert!(!waiting.can_timeout(3600));
+        assert_eq!(
+            error_code(waiting.cancel(&opponent, 3600)),
+            u32::from(UniversalGameError::Unauthorized)
+        );
+        assert_eq!(waiting.cancel(&opponent, 3601).unwrap(), "no_opponent");
+        
+        let mut playing = match_with_seats(&[100, 100]);
+        let creator = playing.creator;
+        assert_eq!(
+            error_code(playing.cancel(&creator, 3600)),
+            u32::from(UniversalGameError::CannotCancel)
+        );
+        assert_eq!(playing.cancel(&creator, 3601).unwrap(), "timeout");
+        assert_eq!(playing.ended_at, Some(3601));
+    }
+    
+    #[test]
+    fn round_history_includes_replayed_draws() {
+        use crate::games::round_manager::RoundManager;
+        
+        let mut table = match_with_seats(&[100, 100]);
+        assert!(!table.has_round_history());
+        
+        let mut round_manager = RoundManager::new(3, 3);
+        table.round_state = round_manager.to_bytes();
+        assert!(!table.has_round_history());
+        
+        // Une égalité rejouée ne compte pas comme round joué mais reste dans l'historique
+        round_manager.process_round_result(GameResult::Draw, 10, vec![0, 0]);
+        assert_eq!(round_manager.rounds_played, 0);
+        table.round_state = round_manager.to_bytes();
+        assert!(table.has_round_history());
+    }
+}
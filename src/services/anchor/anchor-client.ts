@@ -40,13 +40,24 @@ export class RPSClient {
     this.provider = new AnchorProvider(connection, wallet, {});
   }
 
-  // Generate match PDA
-  async getMatchPDA(creator: PublicKey, timestamp: number): Promise<[PublicKey, number]> {
+  // Generate player profile PDA (holds the creator's match nonce)
+  async getPlayerProfilePDA(player: PublicKey): Promise<[PublicKey, number]> {
+    return PublicKey.findProgramAddressSync(
+      [
+        Buffer.from('player_profile'),
+        player.toBuffer()
+      ],
+      RPS_PROGRAM_ID
+    );
+  }
+
+  // Generate match PDA from the creator's profile nonce (0 if no profile yet)
+  async getMatchPDA(creator: PublicKey, matchNonce: BN): Promise<[PublicKey, number]> {
     return PublicKey.findProgramAddressSync(
       [
         Buffer.from('match'),
         creator.toBuffer(),
-        Buffer.from(timestamp.toString())
+        matchNonce.toArrayLike(Buffer, 'le', 8)
       ],
       RPS_PROGRAM_ID
     );
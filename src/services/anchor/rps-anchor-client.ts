@@ -97,7 +97,9 @@ export interface RPSGameState {
   player2Commitment: Uint8Array;
   player1Revealed: RPSChoice | null;
   player2Revealed: RPSChoice | null;
-  revealDeadline: BN;
+  commitDeadline: BN;
+  // Set once both players have committed
+  revealDeadline: BN | null;
 }
 
 export interface CommitmentData {
@@ -625,13 +627,17 @@ export class RPSAnchorClient {
     const player2RevealedValue = gameStateBuffer[67];
     const player2Revealed = player2RevealedFlag === 1 ? player2RevealedValue as RPSChoice : null;
     
-    const revealDeadline = new BN(gameStateBuffer.slice(68, 76), 'le');
+    const commitDeadline = new BN(gameStateBuffer.slice(68, 76), 'le');
+    const revealDeadline = gameStateBuffer[76] === 1
+      ? new BN(gameStateBuffer.slice(77, 85), 'le')
+      : null;
 
     return {
       player1Commitment,
       player2Commitment,
       player1Revealed,
       player2Revealed,
+      commitDeadline,
       revealDeadline
     };
   }
@@ -3,6 +3,7 @@ import { Program } from "@coral-xyz/anchor";
 import { UniversalPvp } from "../target/types/universal_pvp";
 import { expect } from 'chai';
 import { PublicKey, Keypair, SystemProgram, LAMPORTS_PER_SOL } from '@solana/web3.js';
+import { createHash, randomBytes } from 'crypto';
 
 describe("Universal PvP Platform - Comprehensive Test Suite", () => {
   // Configure the client
@@ -20,11 +21,191 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
   let nonParticipant: Keypair;
   
   // Match-specific variables that will be used across tests
-  let matchAccount: Keypair;
+  let matchAccount: PublicKey;
   let vault: PublicKey;
-  let multiRoundMatchAccount: Keypair;
+  let multiRoundMatchAccount: PublicKey;
   let multiRoundVault: PublicKey;
 
+  // RPS choices (Rock = 0, Paper = 1, Scissors = 2)
+  const ROCK = 0;
+  const PAPER = 1;
+  const SCISSORS = 2;
+
+  const sha256 = (data: Buffer): number[] =>
+    Array.from(createHash("sha256").update(data).digest());
+
+  // Table limits enforced by the registry for each game
+  const configBounds = (overrides: object = {}) => ({
+    minPlayers: 2,
+    maxPlayers: 2,
+    minRounds: 1,
+    maxRounds: 3,
+    minBet: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+    maxBet: new anchor.BN(100 * LAMPORTS_PER_SOL),
+    maxCustomParams: Array(16).fill(0),
+    ...overrides,
+  });
+
+  // RPS: commit sha256(choice || salt), then reveal once both players have committed
+  const commitMove = async (match: PublicKey, player: Keypair, choice: number) => {
+    const salt = randomBytes(32);
+    await program.methods
+      .commitMove(sha256(Buffer.concat([Buffer.from([choice]), salt])))
+      .accounts({ matchAccount: match, player: player.publicKey })
+      .signers([player])
+      .rpc();
+    return Array.from(salt);
+  };
+
+  const revealMove = (match: PublicKey, player: Keypair, choice: number, salt: number[]) =>
+    program.methods
+      .revealMove(choice, salt)
+      .accounts({ matchAccount: match, player: player.publicKey })
+      .signers([player])
+      .rpc();
+
+  // Baseline matches live on a fresh keypair, their vault is derived from it
+  // Match PDA: [b"match", creator, match_nonce LE] from the creator's PlayerProfile
+  const nextMatchAddresses = async (creator: PublicKey) => {
+    const [playerProfile] = PublicKey.findProgramAddressSync(
+      [Buffer.from("player_profile"), creator.toBuffer()],
+      program.programId
+    );
+    const profile = await program.account.playerProfile.fetchNullable(playerProfile);
+    const nonce = profile ? profile.matchNonce : new anchor.BN(0);
+
+    const [match] = PublicKey.findProgramAddressSync(
+      [Buffer.from("match"), creator.toBuffer(), nonce.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [matchVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), match.toBuffer()],
+      program.programId
+    );
+    return { playerProfile, match, matchVault };
+  };
+
+  const createMatch = async (
+    creator: Keypair,
+    gameType: object,
+    betAmount: anchor.BN,
+    config: object,
+    invitedOpponent: PublicKey | null = null,
+    joinCodeHash: number[] | null = null,
+  ) => {
+    const { playerProfile, match, matchVault } = await nextMatchAddresses(creator.publicKey);
+
+    await program.methods
+      .createUniversalMatch(gameType, betAmount, config, invitedOpponent, joinCodeHash)
+      .accounts({
+        playerProfile,
+        matchAccount: match,
+        vault: matchVault,
+        matchQueue: null,
+        gameRegistry,
+        creator: creator.publicKey,
+        tokenMint: null,
+        allowedMint: null,
+        creatorTokenAccount: null,
+        vaultTokenAccount: null,
+        tokenProgram: null,
+        systemProgram: SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([creator])
+      .rpc();
+
+    return { match, matchVault };
+  };
+
+  const joinMatch = (
+    match: PublicKey,
+    matchVault: PublicKey,
+    player: Keypair,
+    joinCode: Buffer | null = null,
+  ) =>
+    program.methods
+      .joinMatch(joinCode)
+      .accounts({
+        matchAccount: match,
+        vault: matchVault,
+        matchQueue: null,
+        opponent: player.publicKey,
+        opponentTokenAccount: null,
+        tokenMint: null,
+        vaultTokenAccount: null,
+        tokenProgram: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([player])
+      .rpc();
+
+  // Seats 0 and 1 are refunded through the named accounts, later seats through remaining accounts
+  const cancelMatch = (
+    match: PublicKey,
+    matchVault: PublicKey,
+    requester: Keypair,
+    creator: PublicKey,
+    opponent: PublicKey | null = null,
+    otherSeats: PublicKey[] = [],
+  ) =>
+    program.methods
+      .cancelMatch()
+      .accounts({
+        matchAccount: match,
+        vault: matchVault,
+        matchQueue: null,
+        requester: requester.publicKey,
+        creator,
+        opponent,
+        creatorTokenAccount: null,
+        opponentTokenAccount: null,
+        tokenMint: null,
+        vaultTokenAccount: null,
+        tokenProgram: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(
+        otherSeats.map(pubkey => ({ pubkey, isWritable: true, isSigner: false }))
+      )
+      .signers([requester])
+      .rpc();
+
+  const playRpsRound = async (match: PublicKey, choice1: number, choice2: number) => {
+    const salt1 = await commitMove(match, player1, choice1);
+    const salt2 = await commitMove(match, player2, choice2);
+    await revealMove(match, player1, choice1, salt1);
+    await revealMove(match, player2, choice2, salt2);
+  };
+
+  // Games of chance: every seat commits sha256(seed), then reveals it
+  const commitSeed = async (match: PublicKey, player: Keypair) => {
+    const seed = randomBytes(32);
+    await program.methods
+      .commitSeed(sha256(seed))
+      .accounts({ matchAccount: match, player: player.publicKey })
+      .signers([player])
+      .rpc();
+    return Array.from(seed);
+  };
+
+  const revealSeed = (match: PublicKey, player: Keypair, seed: number[]) =>
+    program.methods
+      .revealSeed(seed)
+      .accounts({ matchAccount: match, randomnessRequest: null, player: player.publicKey })
+      .signers([player])
+      .rpc();
+
+  const exchangeSeeds = async (match: PublicKey, players: Keypair[]) => {
+    const seeds = [];
+    for (const player of players) {
+      seeds.push(await commitSeed(match, player));
+    }
+    for (let i = 0; i < players.length; i++) {
+      await revealSeed(match, players[i], seeds[i]);
+    }
+  };
+
   before(async () => {
     // Setup test accounts
     authority = Keypair.generate();
@@ -32,8 +213,6 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
     player2 = Keypair.generate();
     player3 = Keypair.generate();
     nonParticipant = Keypair.generate();
-    matchAccount = Keypair.generate();
-    multiRoundMatchAccount = Keypair.generate();
 
     // Airdrop SOL to test accounts
     const airdropAmount = 15 * LAMPORTS_PER_SOL;
@@ -53,16 +232,6 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       program.programId
     );
 
-    [vault] = PublicKey.findProgramAddressSync(
-      [Buffer.from("vault"), matchAccount.publicKey.toBuffer()],
-      program.programId
-    );
-
-    [multiRoundVault] = PublicKey.findProgramAddressSync(
-      [Buffer.from("vault"), multiRoundMatchAccount.publicKey.toBuffer()],
-      program.programId
-    );
-
     console.log("🎮 Test setup completed - All accounts funded and PDAs derived");
   });
 
@@ -111,14 +280,15 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       const tx = await program.methods
         .registerGame(
           { rockPaperScissors: {} },
-          "Rock Paper Scissors"
+          "Rock Paper Scissors",
+          configBounds()
         )
         .accounts({
           gameRegistry,
-          creator: player1.publicKey,
+          creator: authority.publicKey,
           systemProgram: SystemProgram.programId,
         })
-        .signers([player1])
+        .signers([authority])
         .rpc();
 
       console.log("✅ RPS game registered:", tx);
@@ -127,52 +297,105 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       expect(registryAccount.totalGames).to.equal(1);
       expect(registryAccount.activeGames[0].name).to.equal("Rock Paper Scissors");
       expect(registryAccount.activeGames[0].gameType).to.deep.equal({ rockPaperScissors: {} });
-      expect(registryAccount.activeGames[0].creator.toString()).to.equal(player1.publicKey.toString());
+      expect(registryAccount.activeGames[0].creator.toString()).to.equal(authority.publicKey.toString());
       expect(registryAccount.activeGames[0].isActive).to.be.true;
-      expect(registryAccount.activeGames[0].totalMatches).to.equal(0);
+      expect(registryAccount.activeGames[0].totalMatches.toNumber()).to.equal(0);
+      expect(registryAccount.activeGames[0].configBounds.maxRounds).to.equal(3);
+    });
+
+    it("Should fail when a non-authority registers a game", async () => {
+      try {
+        await program.methods
+          .registerGame(
+            { dice: {} },
+            "Dice Game",
+            configBounds()
+          )
+          .accounts({
+            gameRegistry,
+            creator: player1.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([player1])
+          .rpc();
+
+        expect.fail("Only the registry authority can register games");
+      } catch (error) {
+        expect(error.message).to.include("Unauthorized");
+        console.log("✅ Non-authority registration correctly rejected");
+      }
+    });
+
+    it("Should fail to register the same game type twice", async () => {
+      try {
+        await program.methods
+          .registerGame(
+            { rockPaperScissors: {} },
+            "Rock Paper Scissors 2",
+            configBounds()
+          )
+          .accounts({
+            gameRegistry,
+            creator: authority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([authority])
+          .rpc();
+
+        expect.fail("Should have failed to register a duplicate game type");
+      } catch (error) {
+        expect(error.message).to.include("GameAlreadyRegistered");
+        console.log("✅ Duplicate game type correctly rejected");
+      }
     });
 
     it("Should register multiple game types", async () => {
-      // Register Dice game
+      // Dice: 2 to 5 dice of up to 20 faces, any scoring rule, up to 4 players
+      const diceParams = Array(16).fill(0);
+      diceParams.splice(0, 6, 5, 20, 3, 99, 1, 1);
       await program.methods
         .registerGame(
           { dice: {} },
-          "Dice Game"
+          "Dice Game",
+          configBounds({ maxPlayers: 4, maxRounds: 5, maxCustomParams: diceParams })
         )
         .accounts({
           gameRegistry,
-          creator: player2.publicKey,
+          creator: authority.publicKey,
           systemProgram: SystemProgram.programId,
         })
-        .signers([player2])
+        .signers([authority])
         .rpc();
 
-      // Register Coin Flip game
+      // Coin Flip: battle royale up to 8 players. The creator's side is checked
+      // by the game itself, not by the custom param bounds
       await program.methods
         .registerGame(
           { coinFlip: {} },
-          "Coin Flip"
+          "Coin Flip",
+          configBounds({ maxPlayers: 8, maxRounds: 1 })
         )
         .accounts({
           gameRegistry,
-          creator: player1.publicKey,
+          creator: authority.publicKey,
           systemProgram: SystemProgram.programId,
         })
-        .signers([player1])
+        .signers([authority])
         .rpc();
 
-      // Register High Card game
+      // High Card
       await program.methods
         .registerGame(
           { highCard: {} },
-          "High Card"
+          "High Card",
+          configBounds({ maxPlayers: 8, maxRounds: 1 })
         )
         .accounts({
           gameRegistry,
-          creator: player2.publicKey,
+          creator: authority.publicKey,
           systemProgram: SystemProgram.programId,
         })
-        .signers([player2])
+        .signers([authority])
         .rpc();
 
       console.log("✅ Multiple games registered successfully");
@@ -190,19 +413,43 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       ]);
     });
 
+    it("Should reject round bounds the round history cannot hold", async () => {
+      try {
+        await program.methods
+          .registerGame(
+            { custom: [1002] },
+            "Custom Game 1002",
+            configBounds({ maxRounds: 9 })
+          )
+          .accounts({
+            gameRegistry,
+            creator: authority.publicKey,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([authority])
+          .rpc();
+
+        expect.fail("Should have failed with too many rounds for round_state");
+      } catch (error) {
+        expect(error.message).to.include("InvalidConfigBounds");
+        console.log("✅ Oversized best-of-N bounds correctly rejected");
+      }
+    });
+
     it("Should register custom game with ID", async () => {
       const customGameId = 1001;
       await program.methods
         .registerGame(
-          { custom: customGameId },
-          "Custom Game 1001"
+          { custom: [customGameId] },
+          "Custom Game 1001",
+          configBounds()
         )
         .accounts({
           gameRegistry,
-          creator: player3.publicKey,
+          creator: authority.publicKey,
           systemProgram: SystemProgram.programId,
         })
-        .signers([player3])
+        .signers([authority])
         .rpc();
 
       const registryAccount = await program.account.gameRegistry.fetch(gameRegistry);
@@ -210,7 +457,7 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       
       const customGame = registryAccount.activeGames.find(g => g.name === "Custom Game 1001");
       expect(customGame).to.exist;
-      expect(customGame.gameType).to.deep.equal({ custom: customGameId });
+      expect(customGame.gameType).to.deep.equal({ custom: { 0: customGameId } });
       
       console.log("✅ Custom game registered with ID:", customGameId);
     });
@@ -229,39 +476,27 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
 
       const initialBalance = await provider.connection.getBalance(player1.publicKey);
 
-      const tx = await program.methods
-        .createUniversalMatch(
-          { rockPaperScissors: {} },
-          betAmount,
-          gameConfig
-        )
-        .accounts({
-          matchAccount: matchAccount.publicKey,
-          vault,
-          creator: player1.publicKey,
-          tokenMint: null,
-          creatorTokenAccount: null,
-          vaultTokenAccount: null,
-          tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([player1, matchAccount])
-        .rpc();
+      ({ match: matchAccount, matchVault: vault } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        betAmount,
+        gameConfig
+      ));
 
-      console.log("✅ RPS Match created:", tx);
+      console.log("✅ RPS Match created:", matchAccount.toString());
 
-      // Verify match state
-      const match = await program.account.universalMatch.fetch(matchAccount.publicKey);
+      // Verify match state: only the creator's stake is in the pot until someone joins
+      const match = await program.account.universalMatch.fetch(matchAccount);
       expect(match.creator.toString()).to.equal(player1.publicKey.toString());
       expect(match.betAmount.toString()).to.equal(betAmount.toString());
       expect(match.status).to.deep.equal({ waitingForOpponent: {} });
-      expect(match.totalPot.toString()).to.equal(betAmount.mul(new anchor.BN(2)).toString());
+      expect(match.totalPot.toString()).to.equal(betAmount.toString());
       expect(match.gameType).to.deep.equal({ rockPaperScissors: {} });
-      expect(match.opponent).to.be.null;
+      expect(match.seats).to.have.length(1);
+      expect(match.seats[0].player.toString()).to.equal(player1.publicKey.toString());
       expect(match.winner).to.be.null;
       expect(match.gameConfig.rounds).to.equal(1);
-      expect(match.timeoutSeconds).to.equal(300); // 5 minutes default
+      expect(match.timeoutSeconds.toNumber()).to.equal(300); // 5 minutes default
       
       // Verify SOL transfer to vault
       const finalBalance = await provider.connection.getBalance(player1.publicKey);
@@ -282,32 +517,13 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         customParams: Array(16).fill(0),
       };
 
-      const newMatchAccount = Keypair.generate();
-      const [newVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), newMatchAccount.publicKey.toBuffer()],
-        program.programId
-      );
-
       try {
-        await program.methods
-          .createUniversalMatch(
-            { rockPaperScissors: {} },
-            invalidBetAmount,
-            gameConfig
-          )
-          .accounts({
-            matchAccount: newMatchAccount.publicKey,
-            vault: newVault,
-            creator: player1.publicKey,
-            tokenMint: null,
-            creatorTokenAccount: null,
-            vaultTokenAccount: null,
-            tokenProgram: null,
-            systemProgram: SystemProgram.programId,
-            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-          })
-          .signers([player1, newMatchAccount])
-          .rpc();
+        await createMatch(
+          player1,
+          { rockPaperScissors: {} },
+          invalidBetAmount,
+          gameConfig
+        );
         
         expect.fail("Should have failed with insufficient bet");
       } catch (error) {
@@ -326,32 +542,13 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         customParams: Array(16).fill(0),
       };
 
-      const newMatchAccount = Keypair.generate();
-      const [newVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), newMatchAccount.publicKey.toBuffer()],
-        program.programId
-      );
-
       try {
-        await program.methods
-          .createUniversalMatch(
-            { rockPaperScissors: {} },
-            excessiveBetAmount,
-            gameConfig
-          )
-          .accounts({
-            matchAccount: newMatchAccount.publicKey,
-            vault: newVault,
-            creator: player1.publicKey,
-            tokenMint: null,
-            creatorTokenAccount: null,
-            vaultTokenAccount: null,
-            tokenProgram: null,
-            systemProgram: SystemProgram.programId,
-            rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-          })
-          .signers([player1, newMatchAccount])
-          .rpc();
+        await createMatch(
+          player1,
+          { rockPaperScissors: {} },
+          excessiveBetAmount,
+          gameConfig
+        );
         
         expect.fail("Should have failed with excessive bet");
       } catch (error) {
@@ -359,6 +556,28 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         console.log("✅ Correctly failed with excessive bet");
       }
     });
+
+    it("Should fail to create match outside the registry bounds", async () => {
+      try {
+        await createMatch(
+          player1,
+          { rockPaperScissors: {} },
+          new anchor.BN(0.1 * LAMPORTS_PER_SOL),
+          {
+            maxPlayers: 2,
+            minBet: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+            maxBet: new anchor.BN(100 * LAMPORTS_PER_SOL),
+            rounds: 5,
+            customParams: Array(16).fill(0),
+          }
+        );
+
+        expect.fail("Should have failed with a config out of bounds");
+      } catch (error) {
+        expect(error.message).to.include("ConfigOutOfBounds");
+        console.log("✅ Correctly failed with rounds above the table limit");
+      }
+    });
   });
 
   describe("3. Join Match Functionality", () => {
@@ -367,12 +586,14 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       const initialVaultBalance = await provider.connection.getBalance(vault);
 
       const tx = await program.methods
-        .joinMatch()
+        .joinMatch(null)
         .accounts({
-          matchAccount: matchAccount.publicKey,
+          matchAccount: matchAccount,
           vault,
+          matchQueue: null,
           opponent: player2.publicKey,
           opponentTokenAccount: null,
+          tokenMint: null,
           vaultTokenAccount: null,
           tokenProgram: null,
           systemProgram: SystemProgram.programId,
@@ -383,10 +604,12 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       console.log("✅ Player 2 joined match:", tx);
 
       // Verify match state
-      const match = await program.account.universalMatch.fetch(matchAccount.publicKey);
-      expect(match.opponent?.toString()).to.equal(player2.publicKey.toString());
+      const match = await program.account.universalMatch.fetch(matchAccount);
+      expect(match.seats).to.have.length(2);
+      expect(match.seats[1].player.toString()).to.equal(player2.publicKey.toString());
       expect(match.status).to.deep.equal({ inProgress: {} });
       expect(match.startedAt).to.not.be.null;
+      expect(match.totalPot.toString()).to.equal(match.betAmount.mul(new anchor.BN(2)).toString());
 
       // Verify SOL transfers
       const finalPlayer2Balance = await provider.connection.getBalance(player2.publicKey);
@@ -401,11 +624,6 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
     });
 
     it("Should fail when non-opponent tries to join completed match", async () => {
-      const completedMatchAccount = Keypair.generate();
-      const [completedVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), completedMatchAccount.publicKey.toBuffer()],
-        program.programId
-      );
 
       // Create and complete a match first
       const betAmount = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
@@ -417,34 +635,23 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         customParams: Array(16).fill(0),
       };
 
-      await program.methods
-        .createUniversalMatch(
-          { rockPaperScissors: {} },
-          betAmount,
-          gameConfig
-        )
-        .accounts({
-          matchAccount: completedMatchAccount.publicKey,
-          vault: completedVault,
-          creator: player1.publicKey,
-          tokenMint: null,
-          creatorTokenAccount: null,
-          vaultTokenAccount: null,
-          tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([player1, completedMatchAccount])
-        .rpc();
+      const { match: completedMatchAccount, matchVault: completedVault } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        betAmount,
+        gameConfig
+      );
 
       // First player joins
       await program.methods
-        .joinMatch()
+        .joinMatch(null)
         .accounts({
-          matchAccount: completedMatchAccount.publicKey,
+          matchAccount: completedMatchAccount,
           vault: completedVault,
+          matchQueue: null,
           opponent: player2.publicKey,
           opponentTokenAccount: null,
+          tokenMint: null,
           vaultTokenAccount: null,
           tokenProgram: null,
           systemProgram: SystemProgram.programId,
@@ -455,12 +662,14 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       // Try to have third player join
       try {
         await program.methods
-          .joinMatch()
+          .joinMatch(null)
           .accounts({
-            matchAccount: completedMatchAccount.publicKey,
+            matchAccount: completedMatchAccount,
             vault: completedVault,
+            matchQueue: null,
             opponent: player3.publicKey,
             opponentTokenAccount: null,
+            tokenMint: null,
             vaultTokenAccount: null,
             tokenProgram: null,
             systemProgram: SystemProgram.programId,
@@ -476,11 +685,6 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
     });
 
     it("Should fail when creator tries to join their own match", async () => {
-      const selfJoinMatchAccount = Keypair.generate();
-      const [selfJoinVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), selfJoinMatchAccount.publicKey.toBuffer()],
-        program.programId
-      );
 
       const betAmount = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
       const gameConfig = {
@@ -491,34 +695,23 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         customParams: Array(16).fill(0),
       };
 
-      await program.methods
-        .createUniversalMatch(
-          { rockPaperScissors: {} },
-          betAmount,
-          gameConfig
-        )
-        .accounts({
-          matchAccount: selfJoinMatchAccount.publicKey,
-          vault: selfJoinVault,
-          creator: player1.publicKey,
-          tokenMint: null,
-          creatorTokenAccount: null,
-          vaultTokenAccount: null,
-          tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([player1, selfJoinMatchAccount])
-        .rpc();
+      const { match: selfJoinMatchAccount, matchVault: selfJoinVault } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        betAmount,
+        gameConfig
+      );
 
       try {
         await program.methods
-          .joinMatch()
+          .joinMatch(null)
           .accounts({
-            matchAccount: selfJoinMatchAccount.publicKey,
+            matchAccount: selfJoinMatchAccount,
             vault: selfJoinVault,
+            matchQueue: null,
             opponent: player1.publicKey, // Same as creator
             opponentTokenAccount: null,
+            tokenMint: null,
             vaultTokenAccount: null,
             tokenProgram: null,
             systemProgram: SystemProgram.programId,
@@ -533,67 +726,151 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         console.log("⚠️ Note: Consider adding validation to prevent self-joining");
       }
     });
-  });
 
-  describe("4. Submit Move Functionality", () => {
-    it("Should allow players to submit valid RPS moves", async () => {
-      // Player 1 submits Rock (0)
-      const move1 = Buffer.from([0]);
-      const tx1 = await program.methods
-        .submitMove(move1)
-        .accounts({
-          matchAccount: matchAccount.publicKey,
-          player: player1.publicKey,
-        })
-        .signers([player1])
-        .rpc();
+    it("Should only let the invited opponent join a private match", async () => {
+      const { match, matchVault } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        new anchor.BN(0.05 * LAMPORTS_PER_SOL),
+        {
+          maxPlayers: 2,
+          minBet: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+          maxBet: new anchor.BN(100 * LAMPORTS_PER_SOL),
+          rounds: 1,
+          customParams: Array(16).fill(0),
+        },
+        player3.publicKey
+      );
 
-      console.log("✅ Player 1 submitted Rock:", tx1);
+      try {
+        await joinMatch(match, matchVault, player2);
 
-      // Player 2 submits Paper (1)  
-      const move2 = Buffer.from([1]);
-      const tx2 = await program.methods
-        .submitMove(move2)
-        .accounts({
-          matchAccount: matchAccount.publicKey,
-          player: player2.publicKey,
-        })
-        .signers([player2])
-        .rpc();
+        expect.fail("Only the invited player can join");
+      } catch (error) {
+        expect(error.message).to.include("MatchInviteOnly");
+      }
 
-      console.log("✅ Player 2 submitted Paper:", tx2);
+      await joinMatch(match, matchVault, player3);
+      const privateMatch = await program.account.universalMatch.fetch(match);
+      expect(privateMatch.seats[1].player.toString()).to.equal(player3.publicKey.toString());
+      console.log("✅ Invite-only match joined by the invited player");
+    });
+
+    it("Should require the join code of a coded match", async () => {
+      // The code hash is salted with the match address: sha256(match || code)
+      const joinCode = Buffer.from("solduel-42");
+      const { match: nextMatch } = await nextMatchAddresses(player2.publicKey);
+      const { match, matchVault } = await createMatch(
+        player2,
+        { rockPaperScissors: {} },
+        new anchor.BN(0.05 * LAMPORTS_PER_SOL),
+        {
+          maxPlayers: 2,
+          minBet: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+          maxBet: new anchor.BN(100 * LAMPORTS_PER_SOL),
+          rounds: 1,
+          customParams: Array(16).fill(0),
+        },
+        null,
+        sha256(Buffer.concat([nextMatch.toBuffer(), joinCode]))
+      );
+
+      try {
+        await joinMatch(match, matchVault, player3, Buffer.from("wrong-code"));
+
+        expect.fail("A wrong join code should be rejected");
+      } catch (error) {
+        expect(error.message).to.include("MatchInviteOnly");
+      }
 
-      // Verify moves were recorded
-      const match = await program.account.universalMatch.fetch(matchAccount.publicKey);
-      expect(match.gameState.length).to.be.greaterThan(2); // Both moves submitted
+      try {
+        await joinMatch(match, matchVault, player3, Buffer.from("solduel"));
+
+        expect.fail("A join code shorter than 8 bytes should be rejected");
+      } catch (error) {
+        expect(error.message).to.include("JoinCodeTooShort");
+      }
+
+      await joinMatch(match, matchVault, player3, joinCode);
+      const codedMatch = await program.account.universalMatch.fetch(match);
+      expect(codedMatch.status).to.deep.equal({ inProgress: {} });
+      console.log("✅ Join code checked before taking a seat");
     });
+  });
 
-    it("Should fail when non-participant tries to submit move", async () => {
+  describe("4. Commit-Reveal Moves", () => {
+    let salt1: number[];
+    let salt2: number[];
+
+    it("Should refuse plain RPS moves", async () => {
       try {
-        const invalidMove = Buffer.from([2]); // Scissors
         await program.methods
-          .submitMove(invalidMove)
+          .submitMove(Buffer.from([ROCK]))
           .accounts({
-            matchAccount: matchAccount.publicKey,
-            player: nonParticipant.publicKey,
+            matchAccount: matchAccount,
+            player: player1.publicKey,
           })
-          .signers([nonParticipant])
+          .signers([player1])
           .rpc();
-        
-        expect.fail("Should have failed - non-participant cannot submit move");
+
+        expect.fail("RPS moves must go through commit-reveal");
+      } catch (error) {
+        expect(error.message).to.include("CommitRevealRequired");
+        console.log("✅ Plain RPS move correctly rejected");
+      }
+    });
+
+    it("Should allow players to commit their RPS moves", async () => {
+      salt1 = await commitMove(matchAccount, player1, ROCK);
+      salt2 = await commitMove(matchAccount, player2, PAPER);
+
+      const match = await program.account.universalMatch.fetch(matchAccount);
+      expect(match.status).to.deep.equal({ inProgress: {} });
+      console.log("✅ Both players committed: Player 1 (Rock), Player 2 (Paper)");
+    });
+
+    it("Should fail when non-participant tries to commit a move", async () => {
+      try {
+        await commitMove(matchAccount, nonParticipant, ROCK);
+
+        expect.fail("Non-participant should not be able to commit");
+      } catch (error) {
+        expect(error.message).to.include("Unauthorized");
+        console.log("✅ Correctly prevented non-participant from committing");
+      }
+    });
+
+    it("Should fail to reveal a move that does not match the commitment", async () => {
+      try {
+        await revealMove(matchAccount, player1, SCISSORS, salt1);
+
+        expect.fail("A different choice should not match the commitment");
       } catch (error) {
-        console.log("✅ Correctly prevented non-participant from submitting move");
+        expect(error.message).to.include("CommitmentVerificationFailed");
+        console.log("✅ Mismatched reveal correctly rejected");
       }
     });
 
     it("Should fail with invalid RPS choice", async () => {
-      const invalidMatchAccount = Keypair.generate();
-      const [invalidVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), invalidMatchAccount.publicKey.toBuffer()],
-        program.programId
-      );
+      try {
+        await revealMove(matchAccount, player1, 5, salt1);
+
+        expect.fail("Should have failed with invalid RPS choice");
+      } catch (error) {
+        expect(error.message).to.include("InvalidChoice");
+        console.log("✅ Correctly rejected invalid RPS choice");
+      }
+    });
+
+    it("Should allow players to reveal their RPS moves", async () => {
+      await revealMove(matchAccount, player1, ROCK, salt1);
+      await revealMove(matchAccount, player2, PAPER, salt2);
+
+      console.log("✅ Both players revealed their moves");
+    });
+
+    it("Should refuse a reveal until both players have committed", async () => {
 
-      // Create a new match for this test
       const betAmount = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
       const gameConfig = {
         maxPlayers: 2,
@@ -603,33 +880,22 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         customParams: Array(16).fill(0),
       };
 
-      await program.methods
-        .createUniversalMatch(
-          { rockPaperScissors: {} },
-          betAmount,
-          gameConfig
-        )
-        .accounts({
-          matchAccount: invalidMatchAccount.publicKey,
-          vault: invalidVault,
-          creator: player1.publicKey,
-          tokenMint: null,
-          creatorTokenAccount: null,
-          vaultTokenAccount: null,
-          tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([player1, invalidMatchAccount])
-        .rpc();
+      const { match: earlyRevealMatchAccount, matchVault: earlyRevealVault } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        betAmount,
+        gameConfig
+      );
 
       await program.methods
-        .joinMatch()
+        .joinMatch(null)
         .accounts({
-          matchAccount: invalidMatchAccount.publicKey,
-          vault: invalidVault,
+          matchAccount: earlyRevealMatchAccount,
+          vault: earlyRevealVault,
+          matchQueue: null,
           opponent: player2.publicKey,
           opponentTokenAccount: null,
+          tokenMint: null,
           vaultTokenAccount: null,
           tokenProgram: null,
           systemProgram: SystemProgram.programId,
@@ -637,21 +903,34 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         .signers([player2])
         .rpc();
 
+      const salt = await commitMove(earlyRevealMatchAccount, player1, ROCK);
+
+      try {
+        await revealMove(earlyRevealMatchAccount, player1, ROCK, salt);
+
+        expect.fail("The reveal window only opens once both players have committed");
+      } catch (error) {
+        expect(error.message).to.include("NotReadyToReveal");
+      }
+
+      // Both committed and one revealed: the other still has the whole reveal window
+      await commitMove(earlyRevealMatchAccount, player2, PAPER);
+      await revealMove(earlyRevealMatchAccount, player1, ROCK, salt);
+
       try {
-        const invalidMove = Buffer.from([5]); // Invalid choice (>2)
         await program.methods
-          .submitMove(invalidMove)
+          .claimTimeoutForfeit()
           .accounts({
-            matchAccount: invalidMatchAccount.publicKey,
-            player: player1.publicKey,
+            matchAccount: earlyRevealMatchAccount,
+            claimer: player1.publicKey,
           })
           .signers([player1])
           .rpc();
-        
-        expect.fail("Should have failed with invalid choice");
+
+        expect.fail("No forfeit before the reveal window has closed");
       } catch (error) {
-        expect(error.message).to.include("InvalidChoice");
-        console.log("✅ Correctly failed with invalid RPS choice");
+        expect(error.message).to.include("NoForfeitToClaim");
+        console.log("✅ Reveal and forfeit wait for both commitments and the reveal window");
       }
     });
   });
@@ -659,30 +938,37 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
   describe("5. Settle Match & Determine Winner", () => {
     it("Should settle match and determine correct winner (Paper beats Rock)", async () => {
       const tx = await program.methods
-        .settleMatch()
+        .settleMatch(false)
         .accounts({
-          matchAccount: matchAccount.publicKey,
+          matchAccount: matchAccount,
+          randomnessRequest: null,
+          vault: null,
+          creator: null,
+          creatorTokenAccount: null,
+          tokenMint: null,
+          vaultTokenAccount: null,
+          tokenProgram: null,
+          systemProgram: null,
         })
         .rpc();
 
       console.log("✅ Match settled:", tx);
 
       // Verify match result
-      const match = await program.account.universalMatch.fetch(matchAccount.publicKey);
+      const match = await program.account.universalMatch.fetch(matchAccount);
       expect(match.status).to.deep.equal({ completed: {} });
       expect(match.winner?.toString()).to.equal(player2.publicKey.toString()); // Paper beats Rock
       expect(match.endedAt).to.not.be.null;
+      expect(match.outcome).to.deep.equal({ winner: { 0: 1 } });
+      expect(match.payouts).to.have.length(1);
+      expect(match.payouts[0].recipient.toString()).to.equal(player2.publicKey.toString());
+      expect(match.payouts[0].amount.toString()).to.equal(match.totalPot.toString());
       
       console.log("✅ Winner correctly determined: Player 2 (Paper) beats Player 1 (Rock)");
     });
 
     it("Should settle draw match correctly", async () => {
       // Create new match for draw test
-      const drawMatchAccount = Keypair.generate();
-      const [drawVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), drawMatchAccount.publicKey.toBuffer()],
-        program.programId
-      );
 
       const betAmount = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
       const gameConfig = {
@@ -694,34 +980,23 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       };
 
       // Create match
-      await program.methods
-        .createUniversalMatch(
-          { rockPaperScissors: {} },
-          betAmount,
-          gameConfig
-        )
-        .accounts({
-          matchAccount: drawMatchAccount.publicKey,
-          vault: drawVault,
-          creator: player1.publicKey,
-          tokenMint: null,
-          creatorTokenAccount: null,
-          vaultTokenAccount: null,
-          tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([player1, drawMatchAccount])
-        .rpc();
+      const { match: drawMatchAccount, matchVault: drawVault } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        betAmount,
+        gameConfig
+      );
 
       // Join match
       await program.methods
-        .joinMatch()
+        .joinMatch(null)
         .accounts({
-          matchAccount: drawMatchAccount.publicKey,
+          matchAccount: drawMatchAccount,
           vault: drawVault,
+          matchQueue: null,
           opponent: player2.publicKey,
           opponentTokenAccount: null,
+          tokenMint: null,
           vaultTokenAccount: null,
           tokenProgram: null,
           systemProgram: SystemProgram.programId,
@@ -730,45 +1005,35 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         .rpc();
 
       // Both players submit Rock (draw)
-      await program.methods
-        .submitMove(Buffer.from([0])) // Rock
-        .accounts({
-          matchAccount: drawMatchAccount.publicKey,
-          player: player1.publicKey,
-        })
-        .signers([player1])
-        .rpc();
-
-      await program.methods
-        .submitMove(Buffer.from([0])) // Rock
-        .accounts({
-          matchAccount: drawMatchAccount.publicKey,
-          player: player2.publicKey,
-        })
-        .signers([player2])
-        .rpc();
+      await playRpsRound(drawMatchAccount, ROCK, ROCK);
 
       // Settle match
       await program.methods
-        .settleMatch()
+        .settleMatch(false)
         .accounts({
-          matchAccount: drawMatchAccount.publicKey,
+          matchAccount: drawMatchAccount,
+          randomnessRequest: null,
+          vault: null,
+          creator: null,
+          creatorTokenAccount: null,
+          tokenMint: null,
+          vaultTokenAccount: null,
+          tokenProgram: null,
+          systemProgram: null,
         })
         .rpc();
 
-      const match = await program.account.universalMatch.fetch(drawMatchAccount.publicKey);
+      const match = await program.account.universalMatch.fetch(drawMatchAccount);
       expect(match.status).to.deep.equal({ completed: {} });
       expect(match.winner).to.be.null; // Draw result
+      // Each seat gets its own stake back
+      expect(match.payouts).to.have.length(2);
+      expect(match.payouts.every(payout => payout.amount.eq(betAmount))).to.be.true;
       
       console.log("✅ Draw match settled correctly - no winner");
     });
 
     it("Should fail to settle match that's not in progress", async () => {
-      const notStartedMatchAccount = Keypair.generate();
-      const [notStartedVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), notStartedMatchAccount.publicKey.toBuffer()],
-        program.programId
-      );
 
       const betAmount = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
       const gameConfig = {
@@ -780,31 +1045,26 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       };
 
       // Create match but don't join
-      await program.methods
-        .createUniversalMatch(
-          { rockPaperScissors: {} },
-          betAmount,
-          gameConfig
-        )
-        .accounts({
-          matchAccount: notStartedMatchAccount.publicKey,
-          vault: notStartedVault,
-          creator: player1.publicKey,
-          tokenMint: null,
-          creatorTokenAccount: null,
-          vaultTokenAccount: null,
-          tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([player1, notStartedMatchAccount])
-        .rpc();
+      const { match: notStartedMatchAccount } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        betAmount,
+        gameConfig
+      );
 
       try {
         await program.methods
-          .settleMatch()
+          .settleMatch(false)
           .accounts({
-            matchAccount: notStartedMatchAccount.publicKey,
+            matchAccount: notStartedMatchAccount,
+            randomnessRequest: null,
+            vault: null,
+            creator: null,
+            creatorTokenAccount: null,
+            tokenMint: null,
+            vaultTokenAccount: null,
+            tokenProgram: null,
+            systemProgram: null,
           })
           .rpc();
         
@@ -814,21 +1074,99 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         console.log("✅ Correctly failed to settle non-started match");
       }
     });
+
+    it("Should pay out and close the match in one transaction with auto_payout", async () => {
+      const { match, matchVault } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        new anchor.BN(0.05 * LAMPORTS_PER_SOL),
+        {
+          maxPlayers: 2,
+          minBet: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+          maxBet: new anchor.BN(100 * LAMPORTS_PER_SOL),
+          rounds: 1,
+          customParams: Array(16).fill(0),
+        }
+      );
+      await joinMatch(match, matchVault, player2);
+      await playRpsRound(match, ROCK, PAPER);
+
+      const autoPayout = (accounts: object, recipients: PublicKey[]) =>
+        program.methods
+          .settleMatch(true)
+          .accounts({
+            matchAccount: match,
+            randomnessRequest: null,
+            vault: matchVault,
+            creator: player1.publicKey,
+            creatorTokenAccount: null,
+            tokenMint: null,
+            vaultTokenAccount: null,
+            tokenProgram: null,
+            systemProgram: SystemProgram.programId,
+            ...accounts,
+          })
+          .remainingAccounts(
+            recipients.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false }))
+          )
+          .rpc();
+
+      try {
+        await autoPayout({ vault: null }, [player2.publicKey]);
+
+        expect.fail("Auto payout needs the vault");
+      } catch (error) {
+        expect(error.message).to.include("MissingPayoutAccount");
+      }
+
+      try {
+        await autoPayout({ systemProgram: null }, [player2.publicKey]);
+
+        expect.fail("Auto payout needs the system program");
+      } catch (error) {
+        expect(error.message).to.include("MissingPayoutAccount");
+      }
+
+      try {
+        await autoPayout({}, []);
+
+        expect.fail("Auto payout needs one account per payout");
+      } catch (error) {
+        expect(error.message).to.include("MissingPayoutAccount");
+      }
+
+      try {
+        await autoPayout({}, [player1.publicKey]);
+
+        expect.fail("The loser's account cannot receive the winner's payout");
+      } catch (error) {
+        expect(error.message).to.include("SeatAccountMismatch");
+      }
+
+      const balanceBefore = await provider.connection.getBalance(player2.publicKey);
+      await autoPayout({}, [player2.publicKey]);
+      const balanceAfter = await provider.connection.getBalance(player2.publicKey);
+
+      expect(balanceAfter - balanceBefore).to.equal(0.1 * LAMPORTS_PER_SOL);
+      expect(await program.account.universalMatch.fetchNullable(match)).to.be.null;
+      console.log("✅ Winner paid and match closed by settle_match");
+    });
   });
 
   describe("6. Claim Winnings - 0% Fees", () => {
     it("Should allow winner to claim 100% of pot (0% fees)", async () => {
       const initialBalance = await provider.connection.getBalance(player2.publicKey);
-      const match = await program.account.universalMatch.fetch(matchAccount.publicKey);
+      const match = await program.account.universalMatch.fetch(matchAccount);
       const expectedWinnings = match.totalPot;
 
       const tx = await program.methods
         .claimWinnings()
         .accounts({
-          matchAccount: matchAccount.publicKey,
+          matchAccount: matchAccount,
           vault,
           claimer: player2.publicKey,
           claimerTokenAccount: null,
+          tokenMint: null,
           vaultTokenAccount: null,
           tokenProgram: null,
           systemProgram: SystemProgram.programId,
@@ -849,17 +1187,64 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       // Verify vault is mostly empty (only rent-exempt minimum remains)
       const vaultBalance = await provider.connection.getBalance(vault);
       expect(vaultBalance).to.be.lessThan(10000000); // Less than 0.01 SOL remaining
+
+      const claimedMatch = await program.account.universalMatch.fetch(matchAccount);
+      expect(claimedMatch.payouts[0].claimed).to.be.true;
+      expect(claimedMatch.seats[1].claimed).to.be.true;
       
       console.log("✅ 0% fees verified: Winner gets ~100% of pot!");
     });
 
+    it("Should fail when the winner claims twice", async () => {
+      try {
+        await program.methods
+          .claimWinnings()
+          .accounts({
+            matchAccount: matchAccount,
+            vault,
+            claimer: player2.publicKey,
+            claimerTokenAccount: null,
+            tokenMint: null,
+            vaultTokenAccount: null,
+            tokenProgram: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([player2])
+          .rpc();
+
+        expect.fail("Winnings can only be claimed once");
+      } catch (error) {
+        expect(error.message).to.include("WinningsAlreadyClaimed");
+        console.log("✅ Second claim correctly rejected");
+      }
+    });
+
+    it("Should fail when the loser tries to claim", async () => {
+      try {
+        await program.methods
+          .claimWinnings()
+          .accounts({
+            matchAccount: matchAccount,
+            vault,
+            claimer: player1.publicKey,
+            claimerTokenAccount: null,
+            tokenMint: null,
+            vaultTokenAccount: null,
+            tokenProgram: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([player1])
+          .rpc();
+
+        expect.fail("The loser has nothing to claim");
+      } catch (error) {
+        expect(error.message).to.include("NothingToClaim");
+        console.log("✅ Loser claim correctly rejected");
+      }
+    });
+
     it("Should allow draw participants to claim their original bets", async () => {
       // Use the draw match from previous test
-      const drawMatchAccount = Keypair.generate();
-      const [drawVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), drawMatchAccount.publicKey.toBuffer()],
-        program.programId
-      );
 
       const betAmount = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
       const gameConfig = {
@@ -871,33 +1256,22 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       };
 
       // Recreate draw scenario
-      await program.methods
-        .createUniversalMatch(
-          { rockPaperScissors: {} },
-          betAmount,
-          gameConfig
-        )
-        .accounts({
-          matchAccount: drawMatchAccount.publicKey,
-          vault: drawVault,
-          creator: player1.publicKey,
-          tokenMint: null,
-          creatorTokenAccount: null,
-          vaultTokenAccount: null,
-          tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([player1, drawMatchAccount])
-        .rpc();
+      const { match: drawMatchAccount, matchVault: drawVault } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        betAmount,
+        gameConfig
+      );
 
       await program.methods
-        .joinMatch()
+        .joinMatch(null)
         .accounts({
-          matchAccount: drawMatchAccount.publicKey,
+          matchAccount: drawMatchAccount,
           vault: drawVault,
+          matchQueue: null,
           opponent: player2.publicKey,
           opponentTokenAccount: null,
+          tokenMint: null,
           vaultTokenAccount: null,
           tokenProgram: null,
           systemProgram: SystemProgram.programId,
@@ -905,28 +1279,20 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         .signers([player2])
         .rpc();
 
-      await program.methods
-        .submitMove(Buffer.from([0]))
-        .accounts({
-          matchAccount: drawMatchAccount.publicKey,
-          player: player1.publicKey,
-        })
-        .signers([player1])
-        .rpc();
-
-      await program.methods
-        .submitMove(Buffer.from([0]))
-        .accounts({
-          matchAccount: drawMatchAccount.publicKey,
-          player: player2.publicKey,
-        })
-        .signers([player2])
-        .rpc();
+      await playRpsRound(drawMatchAccount, ROCK, ROCK);
 
       await program.methods
-        .settleMatch()
+        .settleMatch(false)
         .accounts({
-          matchAccount: drawMatchAccount.publicKey,
+          matchAccount: drawMatchAccount,
+          randomnessRequest: null,
+          vault: null,
+          creator: null,
+          creatorTokenAccount: null,
+          tokenMint: null,
+          vaultTokenAccount: null,
+          tokenProgram: null,
+          systemProgram: null,
         })
         .rpc();
 
@@ -938,10 +1304,11 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       await program.methods
         .claimWinnings()
         .accounts({
-          matchAccount: drawMatchAccount.publicKey,
+          matchAccount: drawMatchAccount,
           vault: drawVault,
           claimer: player1.publicKey,
           claimerTokenAccount: null,
+          tokenMint: null,
           vaultTokenAccount: null,
           tokenProgram: null,
           systemProgram: SystemProgram.programId,
@@ -949,14 +1316,41 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         .signers([player1])
         .rpc();
 
+      // The match cannot be closed while player 2's stake is still in the vault
+      const closeDrawMatch = () =>
+        program.methods
+          .closeMatch()
+          .accounts({
+            matchAccount: drawMatchAccount,
+            vault: drawVault,
+            randomnessRequest: null,
+            creator: player1.publicKey,
+            authority: player2.publicKey,
+            creatorTokenAccount: null,
+            tokenMint: null,
+            vaultTokenAccount: null,
+            tokenProgram: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([player2])
+          .rpc();
+      try {
+        await closeDrawMatch();
+
+        expect.fail("Unclaimed payouts must block closing");
+      } catch (error) {
+        expect(error.message).to.include("UnclaimedPayouts");
+      }
+
       // Player 2 claims their bet back
       await program.methods
         .claimWinnings()
         .accounts({
-          matchAccount: drawMatchAccount.publicKey,
+          matchAccount: drawMatchAccount,
           vault: drawVault,
           claimer: player2.publicKey,
           claimerTokenAccount: null,
+          tokenMint: null,
           vaultTokenAccount: null,
           tokenProgram: null,
           systemProgram: SystemProgram.programId,
@@ -970,16 +1364,19 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       // Both should get their bets back (minus tx fees)
       expect(finalBalance1 - initialBalance1).to.be.greaterThan(betAmount.toNumber() * 0.9);
       expect(finalBalance2 - initialBalance2).to.be.greaterThan(betAmount.toNumber() * 0.9);
+
+      // Every payout is claimed: the match can now be closed, and the rent of
+      // the match and its vault goes back to the creator who paid it
+      const creatorBalanceBeforeClose = await provider.connection.getBalance(player1.publicKey);
+      await closeDrawMatch();
+      expect(await program.account.universalMatch.fetchNullable(drawMatchAccount)).to.be.null;
+      expect(await provider.connection.getBalance(drawVault)).to.equal(0);
+      expect(await provider.connection.getBalance(player1.publicKey)).to.be.greaterThan(creatorBalanceBeforeClose);
       
       console.log("✅ Draw participants successfully claimed their original bets");
     });
 
     it("Should fail when non-participant tries to claim", async () => {
-      const nonParticipantMatchAccount = Keypair.generate();
-      const [nonParticipantVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), nonParticipantMatchAccount.publicKey.toBuffer()],
-        program.programId
-      );
 
       const betAmount = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
       const gameConfig = {
@@ -991,33 +1388,22 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       };
 
       // Complete a full match
-      await program.methods
-        .createUniversalMatch(
-          { rockPaperScissors: {} },
-          betAmount,
-          gameConfig
-        )
-        .accounts({
-          matchAccount: nonParticipantMatchAccount.publicKey,
-          vault: nonParticipantVault,
-          creator: player1.publicKey,
-          tokenMint: null,
-          creatorTokenAccount: null,
-          vaultTokenAccount: null,
-          tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([player1, nonParticipantMatchAccount])
-        .rpc();
+      const { match: nonParticipantMatchAccount, matchVault: nonParticipantVault } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        betAmount,
+        gameConfig
+      );
 
       await program.methods
-        .joinMatch()
+        .joinMatch(null)
         .accounts({
-          matchAccount: nonParticipantMatchAccount.publicKey,
+          matchAccount: nonParticipantMatchAccount,
           vault: nonParticipantVault,
+          matchQueue: null,
           opponent: player2.publicKey,
           opponentTokenAccount: null,
+          tokenMint: null,
           vaultTokenAccount: null,
           tokenProgram: null,
           systemProgram: SystemProgram.programId,
@@ -1025,28 +1411,20 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         .signers([player2])
         .rpc();
 
-      await program.methods
-        .submitMove(Buffer.from([0]))
-        .accounts({
-          matchAccount: nonParticipantMatchAccount.publicKey,
-          player: player1.publicKey,
-        })
-        .signers([player1])
-        .rpc();
-
-      await program.methods
-        .submitMove(Buffer.from([1]))
-        .accounts({
-          matchAccount: nonParticipantMatchAccount.publicKey,
-          player: player2.publicKey,
-        })
-        .signers([player2])
-        .rpc();
+      await playRpsRound(nonParticipantMatchAccount, ROCK, PAPER);
 
       await program.methods
-        .settleMatch()
+        .settleMatch(false)
         .accounts({
-          matchAccount: nonParticipantMatchAccount.publicKey,
+          matchAccount: nonParticipantMatchAccount,
+          randomnessRequest: null,
+          vault: null,
+          creator: null,
+          creatorTokenAccount: null,
+          tokenMint: null,
+          vaultTokenAccount: null,
+          tokenProgram: null,
+          systemProgram: null,
         })
         .rpc();
 
@@ -1055,10 +1433,11 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         await program.methods
           .claimWinnings()
           .accounts({
-            matchAccount: nonParticipantMatchAccount.publicKey,
+            matchAccount: nonParticipantMatchAccount,
             vault: nonParticipantVault,
             claimer: nonParticipant.publicKey,
             claimerTokenAccount: null,
+            tokenMint: null,
             vaultTokenAccount: null,
             tokenProgram: null,
             systemProgram: SystemProgram.programId,
@@ -1074,11 +1453,6 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
     });
 
     it("Should fail to claim from incomplete match", async () => {
-      const incompleteMatchAccount = Keypair.generate();
-      const [incompleteVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), incompleteMatchAccount.publicKey.toBuffer()],
-        program.programId
-      );
 
       const betAmount = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
       const gameConfig = {
@@ -1090,34 +1464,22 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       };
 
       // Create match but don't complete it
-      await program.methods
-        .createUniversalMatch(
-          { rockPaperScissors: {} },
-          betAmount,
-          gameConfig
-        )
-        .accounts({
-          matchAccount: incompleteMatchAccount.publicKey,
-          vault: incompleteVault,
-          creator: player1.publicKey,
-          tokenMint: null,
-          creatorTokenAccount: null,
-          vaultTokenAccount: null,
-          tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([player1, incompleteMatchAccount])
-        .rpc();
+      const { match: incompleteMatchAccount, matchVault: incompleteVault } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        betAmount,
+        gameConfig
+      );
 
       try {
         await program.methods
           .claimWinnings()
           .accounts({
-            matchAccount: incompleteMatchAccount.publicKey,
+            matchAccount: incompleteMatchAccount,
             vault: incompleteVault,
             claimer: player1.publicKey,
             claimerTokenAccount: null,
+            tokenMint: null,
             vaultTokenAccount: null,
             tokenProgram: null,
             systemProgram: SystemProgram.programId,
@@ -1135,11 +1497,6 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
 
   describe("7. Match Cancellation", () => {
     it("Should cancel a match with no opponent and refund creator", async () => {
-      const cancelMatchAccount = Keypair.generate();
-      const [cancelVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), cancelMatchAccount.publicKey.toBuffer()],
-        program.programId
-      );
 
       const betAmount = new anchor.BN(0.1 * LAMPORTS_PER_SOL);
       const gameConfig = {
@@ -1153,37 +1510,26 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       const initialBalance = await provider.connection.getBalance(player1.publicKey);
 
       // Create match
-      await program.methods
-        .createUniversalMatch(
-          { rockPaperScissors: {} },
-          betAmount,
-          gameConfig
-        )
-        .accounts({
-          matchAccount: cancelMatchAccount.publicKey,
-          vault: cancelVault,
-          creator: player1.publicKey,
-          tokenMint: null,
-          creatorTokenAccount: null,
-          vaultTokenAccount: null,
-          tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([player1, cancelMatchAccount])
-        .rpc();
+      const { match: cancelMatchAccount, matchVault: cancelVault } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        betAmount,
+        gameConfig
+      );
 
       // Cancel match
       const tx = await program.methods
         .cancelMatch()
         .accounts({
-          matchAccount: cancelMatchAccount.publicKey,
+          matchAccount: cancelMatchAccount,
           vault: cancelVault,
+          matchQueue: null,
           requester: player1.publicKey,
           creator: player1.publicKey,
           opponent: null,
           creatorTokenAccount: null,
           opponentTokenAccount: null,
+          tokenMint: null,
           vaultTokenAccount: null,
           tokenProgram: null,
           systemProgram: SystemProgram.programId,
@@ -1193,7 +1539,7 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
 
       console.log("✅ Match cancelled:", tx);
 
-      const match = await program.account.universalMatch.fetch(cancelMatchAccount.publicKey);
+      const match = await program.account.universalMatch.fetch(cancelMatchAccount);
       expect(match.status).to.deep.equal({ cancelled: {} });
       expect(match.endedAt).to.not.be.null;
 
@@ -1203,99 +1549,118 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       expect(netLoss).to.be.lessThan(0.01 * LAMPORTS_PER_SOL); // Only transaction fees lost
       
       console.log("✅ Creator successfully refunded after cancellation");
-    });
 
-    it("Should cancel match with both players and refund both", async () => {
-      const cancelMatchAccount2 = Keypair.generate();
-      const [cancelVault2] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), cancelMatchAccount2.publicKey.toBuffer()],
-        program.programId
-      );
+      try {
+        await cancelMatch(cancelMatchAccount, cancelVault, player1, player1.publicKey);
 
-      const betAmount = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
-      const gameConfig = {
-        maxPlayers: 2,
-        minBet: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
-        maxBet: new anchor.BN(100 * LAMPORTS_PER_SOL),
-        rounds: 1,
-        customParams: Array(16).fill(0),
-      };
+        expect.fail("A cancelled match is refunded only once");
+      } catch (error) {
+        expect(error.message).to.include("AlreadyRefunded");
+        console.log("✅ Second cancel correctly rejected");
+      }
 
-      const initialBalance1 = await provider.connection.getBalance(player1.publicKey);
-      const initialBalance2 = await provider.connection.getBalance(player2.publicKey);
-
-      // Create and join match
+      // Any keeper can then close the refunded match for a small bounty
+      const keeperBalance = await provider.connection.getBalance(nonParticipant.publicKey);
+      const creatorBalance = await provider.connection.getBalance(player1.publicKey);
       await program.methods
-        .createUniversalMatch(
-          { rockPaperScissors: {} },
-          betAmount,
-          gameConfig
-        )
+        .cleanupMatch()
         .accounts({
-          matchAccount: cancelMatchAccount2.publicKey,
-          vault: cancelVault2,
+          matchAccount: cancelMatchAccount,
+          vault: cancelVault,
+          randomnessRequest: null,
+          matchQueue: null,
           creator: player1.publicKey,
-          tokenMint: null,
+          keeper: nonParticipant.publicKey,
           creatorTokenAccount: null,
+          tokenMint: null,
           vaultTokenAccount: null,
           tokenProgram: null,
           systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         })
-        .signers([player1, cancelMatchAccount2])
+        .signers([nonParticipant])
         .rpc();
 
-      await program.methods
-        .joinMatch()
-        .accounts({
-          matchAccount: cancelMatchAccount2.publicKey,
-          vault: cancelVault2,
-          opponent: player2.publicKey,
-          opponentTokenAccount: null,
-          vaultTokenAccount: null,
-          tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([player2])
-        .rpc();
+      expect(await program.account.universalMatch.fetchNullable(cancelMatchAccount)).to.be.null;
+      expect(await provider.connection.getBalance(player1.publicKey)).to.be.greaterThan(creatorBalance);
+      expect(await provider.connection.getBalance(nonParticipant.publicKey)).to.be.greaterThan(keeperBalance);
+      console.log("✅ Cancelled match cleaned up by a keeper");
+    });
 
-      // Wait for timeout condition (in a real scenario, you'd wait for the actual timeout)
-      // For testing, we'll simulate timeout by modifying the timeout logic
-      
-      // Cancel match (should refund both players)
-      const tx = await program.methods
-        .cancelMatch()
-        .accounts({
-          matchAccount: cancelMatchAccount2.publicKey,
-          vault: cancelVault2,
-          requester: player1.publicKey,
-          creator: player1.publicKey,
-          opponent: player2.publicKey,
-          creatorTokenAccount: null,
-          opponentTokenAccount: null,
-          vaultTokenAccount: null,
-          tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([player1])
-        .rpc();
+    it("Should refuse to clean up a match that is still in progress", async () => {
+      const { match, matchVault } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        new anchor.BN(0.05 * LAMPORTS_PER_SOL),
+        {
+          maxPlayers: 2,
+          minBet: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+          maxBet: new anchor.BN(100 * LAMPORTS_PER_SOL),
+          rounds: 1,
+          customParams: Array(16).fill(0),
+        }
+      );
+      await joinMatch(match, matchVault, player2);
+
+      try {
+        await program.methods
+          .cleanupMatch()
+          .accounts({
+            matchAccount: match,
+            vault: matchVault,
+            randomnessRequest: null,
+            matchQueue: null,
+            creator: player1.publicKey,
+            keeper: nonParticipant.publicKey,
+            creatorTokenAccount: null,
+            tokenMint: null,
+            vaultTokenAccount: null,
+            tokenProgram: null,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([nonParticipant])
+          .rpc();
 
-      console.log("✅ Match with opponent cancelled:", tx);
+        expect.fail("A match in progress cannot be cleaned up");
+      } catch (error) {
+        expect(error.message).to.include("CleanupNotAllowed");
+        console.log("✅ Cleanup of a live match correctly rejected");
+      }
+    });
 
-      const match = await program.account.universalMatch.fetch(cancelMatchAccount2.publicKey);
-      expect(match.status).to.deep.equal({ cancelled: {} });
+    it("Should only let the creator cancel a table that is still filling", async () => {
+      const betAmount = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
+      const { match, matchVault } = await createMatch(
+        player1,
+        { coinFlip: {} },
+        betAmount,
+        {
+          maxPlayers: 3,
+          minBet: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+          maxBet: new anchor.BN(100 * LAMPORTS_PER_SOL),
+          rounds: 1,
+          customParams: Array(16).fill(0),
+        }
+      );
+      await joinMatch(match, matchVault, player2);
 
-      // Verify both players got refunded (accounting for tx fees)
-      const finalBalance1 = await provider.connection.getBalance(player1.publicKey);
-      const finalBalance2 = await provider.connection.getBalance(player2.publicKey);
-      
-      const netLoss1 = initialBalance1 - finalBalance1;
-      const netLoss2 = initialBalance2 - finalBalance2;
-      
-      expect(netLoss1).to.be.lessThan(0.01 * LAMPORTS_PER_SOL);
-      expect(netLoss2).to.be.lessThan(0.01 * LAMPORTS_PER_SOL);
-      
-      console.log("✅ Both players successfully refunded after cancellation");
+      try {
+        await cancelMatch(match, matchVault, player2, player1.publicKey, player2.publicKey);
+
+        expect.fail("Only the creator can cancel before the wait times out");
+      } catch (error) {
+        expect(error.message).to.include("Unauthorized");
+        console.log("✅ Seated player cannot cancel the creator's table");
+      }
+
+      const initialPlayer2Balance = await provider.connection.getBalance(player2.publicKey);
+      await cancelMatch(match, matchVault, player1, player1.publicKey, player2.publicKey);
+      const finalPlayer2Balance = await provider.connection.getBalance(player2.publicKey);
+
+      const cancelledMatch = await program.account.universalMatch.fetch(match);
+      expect(cancelledMatch.status).to.deep.equal({ cancelled: {} });
+      expect(cancelledMatch.seats.every(seat => "refunded" in seat.status)).to.be.true;
+      expect(finalPlayer2Balance - initialPlayer2Balance).to.equal(betAmount.toNumber());
+      console.log("✅ Creator cancelled and both seats were refunded");
     });
 
     it("Should fail to cancel completed match", async () => {
@@ -1304,13 +1669,15 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         await program.methods
           .cancelMatch()
           .accounts({
-            matchAccount: matchAccount.publicKey,
+            matchAccount: matchAccount,
             vault,
+            matchQueue: null,
             requester: player1.publicKey,
             creator: player1.publicKey,
             opponent: player2.publicKey,
             creatorTokenAccount: null,
             opponentTokenAccount: null,
+            tokenMint: null,
             vaultTokenAccount: null,
             tokenProgram: null,
             systemProgram: SystemProgram.programId,
@@ -1326,11 +1693,6 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
     });
 
     it("Should fail when non-participant tries to cancel", async () => {
-      const cancelMatchAccount3 = Keypair.generate();
-      const [cancelVault3] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), cancelMatchAccount3.publicKey.toBuffer()],
-        program.programId
-      );
 
       const betAmount = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
       const gameConfig = {
@@ -1341,37 +1703,26 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         customParams: Array(16).fill(0),
       };
 
-      await program.methods
-        .createUniversalMatch(
-          { rockPaperScissors: {} },
-          betAmount,
-          gameConfig
-        )
-        .accounts({
-          matchAccount: cancelMatchAccount3.publicKey,
-          vault: cancelVault3,
-          creator: player1.publicKey,
-          tokenMint: null,
-          creatorTokenAccount: null,
-          vaultTokenAccount: null,
-          tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([player1, cancelMatchAccount3])
-        .rpc();
+      const { match: cancelMatchAccount3, matchVault: cancelVault3 } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        betAmount,
+        gameConfig
+      );
 
       try {
         await program.methods
           .cancelMatch()
           .accounts({
-            matchAccount: cancelMatchAccount3.publicKey,
+            matchAccount: cancelMatchAccount3,
             vault: cancelVault3,
+            matchQueue: null,
             requester: nonParticipant.publicKey,
             creator: player1.publicKey,
             opponent: null,
             creatorTokenAccount: null,
             opponentTokenAccount: null,
+            tokenMint: null,
             vaultTokenAccount: null,
             tokenProgram: null,
             systemProgram: SystemProgram.programId,
@@ -1385,15 +1736,95 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         console.log("✅ Correctly prevented non-participant from cancelling");
       }
     });
+
+    it("Should refuse to refund an RPS match once a move is committed", async () => {
+      const { match, matchVault } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        new anchor.BN(0.05 * LAMPORTS_PER_SOL),
+        {
+          maxPlayers: 2,
+          minBet: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+          maxBet: new anchor.BN(100 * LAMPORTS_PER_SOL),
+          rounds: 1,
+          customParams: Array(16).fill(0),
+        }
+      );
+      await joinMatch(match, matchVault, player2);
+      await commitMove(match, player1, SCISSORS);
+
+      try {
+        await cancelMatch(match, matchVault, player1, player1.publicKey, player2.publicKey);
+
+        expect.fail("A committed match must go through the forfeit path");
+      } catch (error) {
+        expect(error.message).to.include("ForfeitRequired");
+        console.log("✅ Committed RPS match cannot be refunded");
+      }
+    });
+
+    it("Should refuse to refund a game of chance once every seed is committed", async () => {
+      const { match, matchVault } = await createMatch(
+        player1,
+        { coinFlip: {} },
+        new anchor.BN(0.05 * LAMPORTS_PER_SOL),
+        {
+          maxPlayers: 2,
+          minBet: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+          maxBet: new anchor.BN(100 * LAMPORTS_PER_SOL),
+          rounds: 1,
+          customParams: Array(16).fill(0),
+        }
+      );
+      await joinMatch(match, matchVault, player2);
+      const seed1 = await commitSeed(match, player1);
+      const seed2 = await commitSeed(match, player2);
+
+      try {
+        await cancelMatch(match, matchVault, player2, player1.publicKey, player2.publicKey);
+
+        expect.fail("The outcome is fixed once every seed is committed");
+      } catch (error) {
+        expect(error.message).to.include("ForfeitRequired");
+        console.log("✅ Committed seeds cannot be refunded");
+      }
+
+      try {
+        await program.methods
+          .claimSeedForfeit()
+          .accounts({ matchAccount: match, claimer: player1.publicKey })
+          .signers([player1])
+          .rpc();
+
+        expect.fail("The seed forfeit waits for the reveal deadline");
+      } catch (error) {
+        expect(error.message).to.include("SeedRevealDeadlineNotPassed");
+        console.log("✅ Seed forfeit refused before the reveal deadline");
+      }
+
+      await revealSeed(match, player1, seed1);
+      await revealSeed(match, player2, seed2);
+      await program.methods
+        .settleMatch(false)
+        .accounts({
+          matchAccount: match,
+          randomnessRequest: null,
+          vault: null,
+          creator: null,
+          creatorTokenAccount: null,
+          tokenMint: null,
+          vaultTokenAccount: null,
+          tokenProgram: null,
+          systemProgram: null,
+        })
+        .rpc();
+      const settledMatch = await program.account.universalMatch.fetch(match);
+      expect(settledMatch.status).to.deep.equal({ completed: {} });
+    });
   });
 
   describe("8. Dispute System", () => {
     it("Should allow participant to dispute a match", async () => {
-      const disputeMatchAccount = Keypair.generate();
-      const [disputeVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), disputeMatchAccount.publicKey.toBuffer()],
-        program.programId
-      );
 
       const betAmount = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
       const gameConfig = {
@@ -1405,33 +1836,22 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       };
 
       // Create complete match
-      await program.methods
-        .createUniversalMatch(
-          { rockPaperScissors: {} },
-          betAmount,
-          gameConfig
-        )
-        .accounts({
-          matchAccount: disputeMatchAccount.publicKey,
-          vault: disputeVault,
-          creator: player1.publicKey,
-          tokenMint: null,
-          creatorTokenAccount: null,
-          vaultTokenAccount: null,
-          tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([player1, disputeMatchAccount])
-        .rpc();
+      const { match: disputeMatchAccount, matchVault: disputeVault } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        betAmount,
+        gameConfig
+      );
 
       await program.methods
-        .joinMatch()
+        .joinMatch(null)
         .accounts({
-          matchAccount: disputeMatchAccount.publicKey,
+          matchAccount: disputeMatchAccount,
           vault: disputeVault,
+          matchQueue: null,
           opponent: player2.publicKey,
           opponentTokenAccount: null,
+          tokenMint: null,
           vaultTokenAccount: null,
           tokenProgram: null,
           systemProgram: SystemProgram.programId,
@@ -1439,28 +1859,20 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         .signers([player2])
         .rpc();
 
-      await program.methods
-        .submitMove(Buffer.from([0]))
-        .accounts({
-          matchAccount: disputeMatchAccount.publicKey,
-          player: player1.publicKey,
-        })
-        .signers([player1])
-        .rpc();
-
-      await program.methods
-        .submitMove(Buffer.from([1]))
-        .accounts({
-          matchAccount: disputeMatchAccount.publicKey,
-          player: player2.publicKey,
-        })
-        .signers([player2])
-        .rpc();
+      await playRpsRound(disputeMatchAccount, ROCK, PAPER);
 
       await program.methods
-        .settleMatch()
+        .settleMatch(false)
         .accounts({
-          matchAccount: disputeMatchAccount.publicKey,
+          matchAccount: disputeMatchAccount,
+          randomnessRequest: null,
+          vault: null,
+          creator: null,
+          creatorTokenAccount: null,
+          tokenMint: null,
+          vaultTokenAccount: null,
+          tokenProgram: null,
+          systemProgram: null,
         })
         .rpc();
 
@@ -1469,7 +1881,7 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       const tx = await program.methods
         .disputeMatch(disputeReason)
         .accounts({
-          matchAccount: disputeMatchAccount.publicKey,
+          matchAccount: disputeMatchAccount,
           disputer: player1.publicKey,
         })
         .signers([player1])
@@ -1477,18 +1889,13 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
 
       console.log("✅ Match disputed:", tx);
 
-      const match = await program.account.universalMatch.fetch(disputeMatchAccount.publicKey);
+      const match = await program.account.universalMatch.fetch(disputeMatchAccount);
       expect(match.status).to.deep.equal({ disputed: {} });
       
       console.log("✅ Match status correctly changed to disputed");
     });
 
     it("Should fail when non-participant tries to dispute", async () => {
-      const disputeMatchAccount2 = Keypair.generate();
-      const [disputeVault2] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), disputeMatchAccount2.publicKey.toBuffer()],
-        program.programId
-      );
 
       const betAmount = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
       const gameConfig = {
@@ -1500,33 +1907,22 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       };
 
       // Create and complete match
-      await program.methods
-        .createUniversalMatch(
-          { rockPaperScissors: {} },
-          betAmount,
-          gameConfig
-        )
-        .accounts({
-          matchAccount: disputeMatchAccount2.publicKey,
-          vault: disputeVault2,
-          creator: player1.publicKey,
-          tokenMint: null,
-          creatorTokenAccount: null,
-          vaultTokenAccount: null,
-          tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([player1, disputeMatchAccount2])
-        .rpc();
+      const { match: disputeMatchAccount2, matchVault: disputeVault2 } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        betAmount,
+        gameConfig
+      );
 
       await program.methods
-        .joinMatch()
+        .joinMatch(null)
         .accounts({
-          matchAccount: disputeMatchAccount2.publicKey,
+          matchAccount: disputeMatchAccount2,
           vault: disputeVault2,
+          matchQueue: null,
           opponent: player2.publicKey,
           opponentTokenAccount: null,
+          tokenMint: null,
           vaultTokenAccount: null,
           tokenProgram: null,
           systemProgram: SystemProgram.programId,
@@ -1539,7 +1935,7 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         await program.methods
           .disputeMatch(disputeReason)
           .accounts({
-            matchAccount: disputeMatchAccount2.publicKey,
+            matchAccount: disputeMatchAccount2,
             disputer: nonParticipant.publicKey,
           })
           .signers([nonParticipant])
@@ -1555,12 +1951,6 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
     it("Should allow authority to resolve dispute", async () => {
       // Note: This test might need to be adjusted based on actual dispute resolution implementation
       // For now, we'll test the structure but the actual resolution depends on having authority access
-      
-      const disputeMatchAccount = Keypair.generate();
-      const [disputeVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), disputeMatchAccount.publicKey.toBuffer()],
-        program.programId
-      );
 
       const betAmount = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
       const gameConfig = {
@@ -1572,33 +1962,22 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       };
 
       // Create, complete, and dispute match
-      await program.methods
-        .createUniversalMatch(
-          { rockPaperScissors: {} },
-          betAmount,
-          gameConfig
-        )
-        .accounts({
-          matchAccount: disputeMatchAccount.publicKey,
-          vault: disputeVault,
-          creator: player1.publicKey,
-          tokenMint: null,
-          creatorTokenAccount: null,
-          vaultTokenAccount: null,
-          tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([player1, disputeMatchAccount])
-        .rpc();
+      const { match: disputeMatchAccount, matchVault: disputeVault } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        betAmount,
+        gameConfig
+      );
 
       await program.methods
-        .joinMatch()
+        .joinMatch(null)
         .accounts({
-          matchAccount: disputeMatchAccount.publicKey,
+          matchAccount: disputeMatchAccount,
           vault: disputeVault,
+          matchQueue: null,
           opponent: player2.publicKey,
           opponentTokenAccount: null,
+          tokenMint: null,
           vaultTokenAccount: null,
           tokenProgram: null,
           systemProgram: SystemProgram.programId,
@@ -1606,35 +1985,27 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         .signers([player2])
         .rpc();
 
-      await program.methods
-        .submitMove(Buffer.from([0]))
-        .accounts({
-          matchAccount: disputeMatchAccount.publicKey,
-          player: player1.publicKey,
-        })
-        .signers([player1])
-        .rpc();
-
-      await program.methods
-        .submitMove(Buffer.from([1]))
-        .accounts({
-          matchAccount: disputeMatchAccount.publicKey,
-          player: player2.publicKey,
-        })
-        .signers([player2])
-        .rpc();
+      await playRpsRound(disputeMatchAccount, ROCK, PAPER);
 
       await program.methods
-        .settleMatch()
+        .settleMatch(false)
         .accounts({
-          matchAccount: disputeMatchAccount.publicKey,
+          matchAccount: disputeMatchAccount,
+          randomnessRequest: null,
+          vault: null,
+          creator: null,
+          creatorTokenAccount: null,
+          tokenMint: null,
+          vaultTokenAccount: null,
+          tokenProgram: null,
+          systemProgram: null,
         })
         .rpc();
 
       await program.methods
         .disputeMatch("Test dispute")
         .accounts({
-          matchAccount: disputeMatchAccount.publicKey,
+          matchAccount: disputeMatchAccount,
           disputer: player1.publicKey,
         })
         .signers([player1])
@@ -1645,7 +2016,7 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         await program.methods
           .resolveDispute({ player2Wins: {} })
           .accounts({
-            matchAccount: disputeMatchAccount.publicKey,
+            matchAccount: disputeMatchAccount,
             gameRegistry,
             authority: authority.publicKey,
           })
@@ -1654,7 +2025,7 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
 
         console.log("✅ Dispute resolved by authority");
         
-        const match = await program.account.universalMatch.fetch(disputeMatchAccount.publicKey);
+        const match = await program.account.universalMatch.fetch(disputeMatchAccount);
         expect(match.status).to.deep.equal({ completed: {} });
         expect(match.winner?.toString()).to.equal(player2.publicKey.toString());
         
@@ -1663,6 +2034,65 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         console.log("⚠️ Dispute resolution test - authority validation working");
       }
     });
+
+    it("Should refuse disputes once a payout has been claimed", async () => {
+      const { match, matchVault } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        new anchor.BN(0.05 * LAMPORTS_PER_SOL),
+        {
+          maxPlayers: 2,
+          minBet: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+          maxBet: new anchor.BN(100 * LAMPORTS_PER_SOL),
+          rounds: 1,
+          customParams: Array(16).fill(0),
+        }
+      );
+      await joinMatch(match, matchVault, player2);
+      await playRpsRound(match, ROCK, PAPER);
+      await program.methods
+        .settleMatch(false)
+        .accounts({
+          matchAccount: match,
+          randomnessRequest: null,
+          vault: null,
+          creator: null,
+          creatorTokenAccount: null,
+          tokenMint: null,
+          vaultTokenAccount: null,
+          tokenProgram: null,
+          systemProgram: null,
+        })
+        .rpc();
+
+      await program.methods
+        .claimWinnings()
+        .accounts({
+          matchAccount: match,
+          vault: matchVault,
+          claimer: player2.publicKey,
+          claimerTokenAccount: null,
+          tokenMint: null,
+          vaultTokenAccount: null,
+          tokenProgram: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([player2])
+        .rpc();
+
+      try {
+        await program.methods
+          .disputeMatch("Too late")
+          .accounts({ matchAccount: match, disputer: player1.publicKey })
+          .signers([player1])
+          .rpc();
+
+        expect.fail("A paid-out match cannot be disputed");
+      } catch (error) {
+        expect(error.message).to.include("CannotDispute");
+        console.log("✅ Dispute after a claim correctly rejected");
+      }
+    });
   });
 
   describe("9. Multi-Round System", () => {
@@ -1677,34 +2107,23 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       };
 
       // Create multi-round match
-      await program.methods
-        .createUniversalMatch(
-          { rockPaperScissors: {} },
-          betAmount,
-          gameConfig
-        )
-        .accounts({
-          matchAccount: multiRoundMatchAccount.publicKey,
-          vault: multiRoundVault,
-          creator: player1.publicKey,
-          tokenMint: null,
-          creatorTokenAccount: null,
-          vaultTokenAccount: null,
-          tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([player1, multiRoundMatchAccount])
-        .rpc();
+      ({ match: multiRoundMatchAccount, matchVault: multiRoundVault } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        betAmount,
+        gameConfig
+      ));
 
       // Join match
       await program.methods
-        .joinMatch()
+        .joinMatch(null)
         .accounts({
-          matchAccount: multiRoundMatchAccount.publicKey,
+          matchAccount: multiRoundMatchAccount,
           vault: multiRoundVault,
+          matchQueue: null,
           opponent: player2.publicKey,
           opponentTokenAccount: null,
+          tokenMint: null,
           vaultTokenAccount: null,
           tokenProgram: null,
           systemProgram: SystemProgram.programId,
@@ -1712,7 +2131,7 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         .signers([player2])
         .rpc();
 
-      const match = await program.account.universalMatch.fetch(multiRoundMatchAccount.publicKey);
+      const match = await program.account.universalMatch.fetch(multiRoundMatchAccount);
       expect(match.gameConfig.rounds).to.equal(3);
       expect(match.roundState.length).to.be.greaterThan(0);
       
@@ -1721,33 +2140,25 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
 
     it("Should play round 1 - Player 2 wins", async () => {
       // Round 1: Player 1 = Rock, Player 2 = Paper
+      await playRpsRound(multiRoundMatchAccount, ROCK, PAPER);
+
+      // Settle round 1
       await program.methods
-        .submitMove(Buffer.from([0])) // Rock
+        .settleMatch(false)
         .accounts({
-          matchAccount: multiRoundMatchAccount.publicKey,
-          player: player1.publicKey,
-        })
-        .signers([player1])
-        .rpc();
-
-      await program.methods
-        .submitMove(Buffer.from([1])) // Paper
-        .accounts({
-          matchAccount: multiRoundMatchAccount.publicKey,
-          player: player2.publicKey,
-        })
-        .signers([player2])
-        .rpc();
-
-      // Settle round 1
-      await program.methods
-        .settleMatch()
-        .accounts({
-          matchAccount: multiRoundMatchAccount.publicKey,
+          matchAccount: multiRoundMatchAccount,
+          randomnessRequest: null,
+          vault: null,
+          creator: null,
+          creatorTokenAccount: null,
+          tokenMint: null,
+          vaultTokenAccount: null,
+          tokenProgram: null,
+          systemProgram: null,
         })
         .rpc();
 
-      const match = await program.account.universalMatch.fetch(multiRoundMatchAccount.publicKey);
+      const match = await program.account.universalMatch.fetch(multiRoundMatchAccount);
       expect(match.status).to.deep.equal({ inProgress: {} }); // Match continues
       expect(match.winner).to.be.null; // No overall winner yet
 
@@ -1756,33 +2167,25 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
 
     it("Should play round 2 - Player 1 wins", async () => {
       // Round 2: Player 1 = Scissors, Player 2 = Paper  
-      await program.methods
-        .submitMove(Buffer.from([2])) // Scissors
-        .accounts({
-          matchAccount: multiRoundMatchAccount.publicKey,
-          player: player1.publicKey,
-        })
-        .signers([player1])
-        .rpc();
-
-      await program.methods
-        .submitMove(Buffer.from([1])) // Paper
-        .accounts({
-          matchAccount: multiRoundMatchAccount.publicKey,
-          player: player2.publicKey,
-        })
-        .signers([player2])
-        .rpc();
+      await playRpsRound(multiRoundMatchAccount, SCISSORS, PAPER);
 
       // Settle round 2
       await program.methods
-        .settleMatch()
+        .settleMatch(false)
         .accounts({
-          matchAccount: multiRoundMatchAccount.publicKey,
+          matchAccount: multiRoundMatchAccount,
+          randomnessRequest: null,
+          vault: null,
+          creator: null,
+          creatorTokenAccount: null,
+          tokenMint: null,
+          vaultTokenAccount: null,
+          tokenProgram: null,
+          systemProgram: null,
         })
         .rpc();
 
-      const match = await program.account.universalMatch.fetch(multiRoundMatchAccount.publicKey);
+      const match = await program.account.universalMatch.fetch(multiRoundMatchAccount);
       expect(match.status).to.deep.equal({ inProgress: {} }); // Match continues
       expect(match.winner).to.be.null; // No overall winner yet
 
@@ -1791,33 +2194,25 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
 
     it("Should play round 3 (tiebreaker) - Player 2 wins match", async () => {
       // Round 3: Player 1 = Rock, Player 2 = Paper
-      await program.methods
-        .submitMove(Buffer.from([0])) // Rock
-        .accounts({
-          matchAccount: multiRoundMatchAccount.publicKey,
-          player: player1.publicKey,
-        })
-        .signers([player1])
-        .rpc();
-
-      await program.methods
-        .submitMove(Buffer.from([1])) // Paper
-        .accounts({
-          matchAccount: multiRoundMatchAccount.publicKey,
-          player: player2.publicKey,
-        })
-        .signers([player2])
-        .rpc();
+      await playRpsRound(multiRoundMatchAccount, ROCK, PAPER);
 
       // Settle final round
       await program.methods
-        .settleMatch()
+        .settleMatch(false)
         .accounts({
-          matchAccount: multiRoundMatchAccount.publicKey,
+          matchAccount: multiRoundMatchAccount,
+          randomnessRequest: null,
+          vault: null,
+          creator: null,
+          creatorTokenAccount: null,
+          tokenMint: null,
+          vaultTokenAccount: null,
+          tokenProgram: null,
+          systemProgram: null,
         })
         .rpc();
 
-      const match = await program.account.universalMatch.fetch(multiRoundMatchAccount.publicKey);
+      const match = await program.account.universalMatch.fetch(multiRoundMatchAccount);
       expect(match.status).to.deep.equal({ completed: {} }); // Match finished
       expect(match.winner?.toString()).to.equal(player2.publicKey.toString()); // Player 2 wins 2-1
 
@@ -1825,11 +2220,6 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
     });
 
     it("Should handle draw rounds in multi-round match", async () => {
-      const drawRoundsMatchAccount = Keypair.generate();
-      const [drawRoundsVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), drawRoundsMatchAccount.publicKey.toBuffer()],
-        program.programId
-      );
 
       const betAmount = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
       const gameConfig = {
@@ -1841,33 +2231,22 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       };
 
       // Create and join match
-      await program.methods
-        .createUniversalMatch(
-          { rockPaperScissors: {} },
-          betAmount,
-          gameConfig
-        )
-        .accounts({
-          matchAccount: drawRoundsMatchAccount.publicKey,
-          vault: drawRoundsVault,
-          creator: player1.publicKey,
-          tokenMint: null,
-          creatorTokenAccount: null,
-          vaultTokenAccount: null,
-          tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([player1, drawRoundsMatchAccount])
-        .rpc();
+      const { match: drawRoundsMatchAccount, matchVault: drawRoundsVault } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        betAmount,
+        gameConfig
+      );
 
       await program.methods
-        .joinMatch()
+        .joinMatch(null)
         .accounts({
-          matchAccount: drawRoundsMatchAccount.publicKey,
+          matchAccount: drawRoundsMatchAccount,
           vault: drawRoundsVault,
+          matchQueue: null,
           opponent: player2.publicKey,
           opponentTokenAccount: null,
+          tokenMint: null,
           vaultTokenAccount: null,
           tokenProgram: null,
           systemProgram: SystemProgram.programId,
@@ -1876,64 +2255,48 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         .rpc();
 
       // Play a draw round (both choose Rock)
-      await program.methods
-        .submitMove(Buffer.from([0])) // Rock
-        .accounts({
-          matchAccount: drawRoundsMatchAccount.publicKey,
-          player: player1.publicKey,
-        })
-        .signers([player1])
-        .rpc();
-
-      await program.methods
-        .submitMove(Buffer.from([0])) // Rock  
-        .accounts({
-          matchAccount: drawRoundsMatchAccount.publicKey,
-          player: player2.publicKey,
-        })
-        .signers([player2])
-        .rpc();
+      await playRpsRound(drawRoundsMatchAccount, ROCK, ROCK);
 
       // Settle draw round
       await program.methods
-        .settleMatch()
+        .settleMatch(false)
         .accounts({
-          matchAccount: drawRoundsMatchAccount.publicKey,
+          matchAccount: drawRoundsMatchAccount,
+          randomnessRequest: null,
+          vault: null,
+          creator: null,
+          creatorTokenAccount: null,
+          tokenMint: null,
+          vaultTokenAccount: null,
+          tokenProgram: null,
+          systemProgram: null,
         })
         .rpc();
 
-      let match = await program.account.universalMatch.fetch(drawRoundsMatchAccount.publicKey);
+      let match = await program.account.universalMatch.fetch(drawRoundsMatchAccount);
       expect(match.status).to.deep.equal({ inProgress: {} }); // Match continues after draw
       
       console.log("✅ Draw round handled correctly - match continues");
 
       // Complete with a decisive round
-      await program.methods
-        .submitMove(Buffer.from([0])) // Rock
-        .accounts({
-          matchAccount: drawRoundsMatchAccount.publicKey,
-          player: player1.publicKey,
-        })
-        .signers([player1])
-        .rpc();
+      await playRpsRound(drawRoundsMatchAccount, ROCK, PAPER);
 
       await program.methods
-        .submitMove(Buffer.from([1])) // Paper
+        .settleMatch(false)
         .accounts({
-          matchAccount: drawRoundsMatchAccount.publicKey,
-          player: player2.publicKey,
-        })
-        .signers([player2])
-        .rpc();
-
-      await program.methods
-        .settleMatch()
-        .accounts({
-          matchAccount: drawRoundsMatchAccount.publicKey,
+          matchAccount: drawRoundsMatchAccount,
+          randomnessRequest: null,
+          vault: null,
+          creator: null,
+          creatorTokenAccount: null,
+          tokenMint: null,
+          vaultTokenAccount: null,
+          tokenProgram: null,
+          systemProgram: null,
         })
         .rpc();
 
-      match = await program.account.universalMatch.fetch(drawRoundsMatchAccount.publicKey);
+      match = await program.account.universalMatch.fetch(drawRoundsMatchAccount);
       // The match might still be in progress depending on the round manager logic
       // This tests that the system can handle draw rounds properly
       
@@ -1942,62 +2305,43 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
   });
 
   describe("10. Different Game Types", () => {
-    let diceMatchAccount: Keypair;
+    let diceMatchAccount: PublicKey;
     let diceVault: PublicKey;
 
-    before(() => {
-      diceMatchAccount = Keypair.generate();
-      [diceVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), diceMatchAccount.publicKey.toBuffer()],
-        program.programId
-      );
-    });
-
     it("Should create and play Dice game", async () => {
       const betAmount = new anchor.BN(0.05 * LAMPORTS_PER_SOL);
       const gameConfig = {
         maxPlayers: 2,
-        minBet: new anchor.BN(0.005 * LAMPORTS_PER_SOL),
-        maxBet: new anchor.BN(1000 * LAMPORTS_PER_SOL),
+        minBet: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+        maxBet: new anchor.BN(100 * LAMPORTS_PER_SOL),
         rounds: 1,
         customParams: Array(16).fill(0),
       };
 
-      const tx = await program.methods
-        .createUniversalMatch(
-          { dice: {} },
-          betAmount,
-          gameConfig
-        )
-        .accounts({
-          matchAccount: diceMatchAccount.publicKey,
-          vault: diceVault,
-          creator: player1.publicKey,
-          tokenMint: null,
-          creatorTokenAccount: null,
-          vaultTokenAccount: null,
-          tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([player1, diceMatchAccount])
-        .rpc();
+      ({ match: diceMatchAccount, matchVault: diceVault } = await createMatch(
+        player1,
+        { dice: {} },
+        betAmount,
+        gameConfig
+      ));
 
-      console.log("✅ Dice match created:", tx);
+      console.log("✅ Dice match created:", diceMatchAccount.toString());
 
-      const match = await program.account.universalMatch.fetch(diceMatchAccount.publicKey);
+      const match = await program.account.universalMatch.fetch(diceMatchAccount);
       expect(match.gameType).to.deep.equal({ dice: {} });
     });
 
     it("Should play complete Dice game", async () => {
       // Join match
       await program.methods
-        .joinMatch()
+        .joinMatch(null)
         .accounts({
-          matchAccount: diceMatchAccount.publicKey,
+          matchAccount: diceMatchAccount,
           vault: diceVault,
+          matchQueue: null,
           opponent: player2.publicKey,
           opponentTokenAccount: null,
+          tokenMint: null,
           vaultTokenAccount: null,
           tokenProgram: null,
           systemProgram: SystemProgram.programId,
@@ -2005,220 +2349,243 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
         .signers([player2])
         .rpc();
 
-      // Submit dice rolls (simulated)
-      const dice1Move = Buffer.from([4, 3]); // Total: 7
+      try {
+        await program.methods
+          .submitMove(Buffer.from([6, 6]))
+          .accounts({
+            matchAccount: diceMatchAccount,
+            player: player1.publicKey,
+          })
+          .signers([player1])
+          .rpc();
+
+        expect.fail("Players cannot choose their dice");
+      } catch (error) {
+        expect(error.message).to.include("PlayerRollsNotAllowed");
+      }
+
+      await exchangeSeeds(diceMatchAccount, [player1, player2]);
       await program.methods
-        .submitMove(dice1Move)
+        .rollDice()
         .accounts({
-          matchAccount: diceMatchAccount.publicKey,
+          matchAccount: diceMatchAccount,
+          randomnessRequest: null,
           player: player1.publicKey,
         })
         .signers([player1])
         .rpc();
 
-      const dice2Move = Buffer.from([5, 6]); // Total: 11
-      await program.methods
-        .submitMove(dice2Move)
-        .accounts({
-          matchAccount: diceMatchAccount.publicKey,
-          player: player2.publicKey,
-        })
-        .signers([player2])
-        .rpc();
-
       // Settle match
       await program.methods
-        .settleMatch()
+        .settleMatch(false)
         .accounts({
-          matchAccount: diceMatchAccount.publicKey,
+          matchAccount: diceMatchAccount,
+          randomnessRequest: null,
+          vault: null,
+          creator: null,
+          creatorTokenAccount: null,
+          tokenMint: null,
+          vaultTokenAccount: null,
+          tokenProgram: null,
+          systemProgram: null,
         })
         .rpc();
 
-      const match = await program.account.universalMatch.fetch(diceMatchAccount.publicKey);
+      const match = await program.account.universalMatch.fetch(diceMatchAccount);
       expect(match.status).to.deep.equal({ completed: {} });
-      expect(match.winner?.toString()).to.equal(player2.publicKey.toString()); // 11 > 7
-      
-      console.log("✅ Dice game completed - Player 2 wins with total 11 vs 7");
+      const paid = match.payouts.reduce((sum, payout) => sum.add(payout.amount), new anchor.BN(0));
+      expect(paid.toString()).to.equal(match.totalPot.toString());
+      console.log("✅ Dice game rolled from both seeds and settled");
     });
 
     it("Should create and test Coin Flip game", async () => {
-      const coinFlipMatchAccount = Keypair.generate();
-      const [coinFlipVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), coinFlipMatchAccount.publicKey.toBuffer()],
-        program.programId
+      const tails = Array(16).fill(0);
+      tails[0] = 1;
+      const { match, matchVault } = await createMatch(
+        player1,
+        { coinFlip: {} },
+        new anchor.BN(0.03 * LAMPORTS_PER_SOL),
+        {
+          maxPlayers: 2,
+          minBet: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+          maxBet: new anchor.BN(10 * LAMPORTS_PER_SOL),
+          rounds: 1,
+          customParams: tails, // customParams[0] = 1: creator picks tails
+        }
       );
+      await joinMatch(match, matchVault, player2);
 
-      const betAmount = new anchor.BN(0.03 * LAMPORTS_PER_SOL);
-      const gameConfig = {
-        maxPlayers: 2,
-        minBet: new anchor.BN(0.005 * LAMPORTS_PER_SOL),
-        maxBet: new anchor.BN(10 * LAMPORTS_PER_SOL),
-        rounds: 1,
-        customParams: Array(16).fill(0),
-      };
+      try {
+        await program.methods
+          .settleMatch(false)
+          .accounts({
+            matchAccount: match,
+            randomnessRequest: null,
+            vault: null,
+            creator: null,
+            creatorTokenAccount: null,
+            tokenMint: null,
+            vaultTokenAccount: null,
+            tokenProgram: null,
+            systemProgram: null,
+          })
+          .rpc();
 
-      // Create coin flip match
+        expect.fail("The coin cannot be flipped before the seeds are revealed");
+      } catch (error) {
+        expect(error.message).to.include("MovesNotSubmitted");
+      }
+
+      // The coin is drawn from both players' committed seeds
+      await exchangeSeeds(match, [player1, player2]);
       await program.methods
-        .createUniversalMatch(
-          { coinFlip: {} },
-          betAmount,
-          gameConfig
-        )
+        .settleMatch(false)
         .accounts({
-          matchAccount: coinFlipMatchAccount.publicKey,
-          vault: coinFlipVault,
-          creator: player1.publicKey,
-          tokenMint: null,
+          matchAccount: match,
+          randomnessRequest: null,
+          vault: null,
+          creator: null,
           creatorTokenAccount: null,
+          tokenMint: null,
           vaultTokenAccount: null,
           tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+          systemProgram: null,
         })
-        .signers([player1, coinFlipMatchAccount])
         .rpc();
 
-      // Join and play
+      const coinFlipMatch = await program.account.universalMatch.fetch(match);
+      expect(coinFlipMatch.status).to.deep.equal({ completed: {} });
+      expect(coinFlipMatch.gameType).to.deep.equal({ coinFlip: {} });
+      expect([player1.publicKey.toString(), player2.publicKey.toString()])
+        .to.include(coinFlipMatch.winner?.toString());
+      console.log("✅ Coin flip settled from both seeds");
+    });
+
+    it("Should play a three-player Coin Flip battle royale", async () => {
+      const { match, matchVault } = await createMatch(
+        player1,
+        { coinFlip: {} },
+        new anchor.BN(0.1 * LAMPORTS_PER_SOL),
+        {
+          maxPlayers: 3,
+          minBet: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+          maxBet: new anchor.BN(10 * LAMPORTS_PER_SOL),
+          rounds: 1,
+          customParams: Array(16).fill(0),
+        }
+      );
+      await joinMatch(match, matchVault, player2);
+
+      // The table stays open until the last seat is taken
+      let royale = await program.account.universalMatch.fetch(match);
+      expect(royale.status).to.deep.equal({ waitingForOpponent: {} });
+
+      await joinMatch(match, matchVault, player3);
+      royale = await program.account.universalMatch.fetch(match);
+      expect(royale.status).to.deep.equal({ inProgress: {} });
+      expect(royale.seats).to.have.length(3);
+
+      await exchangeSeeds(match, [player1, player2, player3]);
       await program.methods
-        .joinMatch()
+        .settleMatch(false)
         .accounts({
-          matchAccount: coinFlipMatchAccount.publicKey,
-          vault: coinFlipVault,
-          opponent: player2.publicKey,
-          opponentTokenAccount: null,
+          matchAccount: match,
+          randomnessRequest: null,
+          vault: null,
+          creator: null,
+          creatorTokenAccount: null,
+          tokenMint: null,
           vaultTokenAccount: null,
           tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([player2])
-        .rpc();
-
-      // Submit choices (0 = heads, 1 = tails)
-      await program.methods
-        .submitMove(Buffer.from([0])) // Heads
-        .accounts({
-          matchAccount: coinFlipMatchAccount.publicKey,
-          player: player1.publicKey,
+          systemProgram: null,
         })
-        .signers([player1])
         .rpc();
 
-      await program.methods
-        .submitMove(Buffer.from([1])) // Tails
-        .accounts({
-          matchAccount: coinFlipMatchAccount.publicKey,
-          player: player2.publicKey,
-        })
-        .signers([player2])
-        .rpc();
+      royale = await program.account.universalMatch.fetch(match);
+      expect(royale.status).to.deep.equal({ completed: {} });
+      expect(royale.payouts).to.have.length(1);
+      expect(royale.payouts[0].amount.toString()).to.equal(royale.totalPot.toString());
+      console.log("✅ Battle royale survivor takes the whole pot");
+    });
 
-      // Settle match
-      await program.methods
-        .settleMatch()
-        .accounts({
-          matchAccount: coinFlipMatchAccount.publicKey,
-        })
-        .rpc();
+    it("Should reject a coin flip side other than heads or tails", async () => {
+      const invalidSide = Array(16).fill(0);
+      invalidSide[0] = 2;
 
-      const match = await program.account.universalMatch.fetch(coinFlipMatchAccount.publicKey);
-      expect(match.status).to.deep.equal({ completed: {} });
-      expect(match.gameType).to.deep.equal({ coinFlip: {} });
-      
-      console.log("✅ Coin Flip game completed - Winner determined by pseudo-random result");
+      try {
+        await createMatch(
+          player1,
+          { coinFlip: {} },
+          new anchor.BN(0.03 * LAMPORTS_PER_SOL),
+          {
+            maxPlayers: 2,
+            minBet: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+            maxBet: new anchor.BN(10 * LAMPORTS_PER_SOL),
+            rounds: 1,
+            customParams: invalidSide,
+          }
+        );
+
+        expect.fail("Side 2 is neither heads nor tails");
+      } catch (error) {
+        expect(error.message).to.include("InvalidSide");
+        console.log("✅ Invalid coin side rejected at match creation");
+      }
     });
 
     it("Should create and test High Card game", async () => {
-      const highCardMatchAccount = Keypair.generate();
-      const [highCardVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), highCardMatchAccount.publicKey.toBuffer()],
-        program.programId
+      const { match, matchVault } = await createMatch(
+        player1,
+        { highCard: {} },
+        new anchor.BN(0.1 * LAMPORTS_PER_SOL),
+        {
+          maxPlayers: 2,
+          minBet: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+          maxBet: new anchor.BN(100 * LAMPORTS_PER_SOL),
+          rounds: 1,
+          customParams: Array(16).fill(0),
+        }
       );
+      await joinMatch(match, matchVault, player2);
 
-      const betAmount = new anchor.BN(0.02 * LAMPORTS_PER_SOL);
-      const gameConfig = {
-        maxPlayers: 2,
-        minBet: new anchor.BN(0.005 * LAMPORTS_PER_SOL),
-        maxBet: new anchor.BN(5 * LAMPORTS_PER_SOL),
-        rounds: 1,
-        customParams: Array(16).fill(0),
-      };
+      // Seeds are revealed only once every seat has committed
+      const seed1 = await commitSeed(match, player1);
+      try {
+        await revealSeed(match, player1, seed1);
 
-      // Create high card match
+        expect.fail("Seeds cannot be revealed before every seat commits");
+      } catch (error) {
+        expect(error.message).to.include("RandomnessNotReady");
+      }
+      const seed2 = await commitSeed(match, player2);
+      await revealSeed(match, player1, seed1);
+      await revealSeed(match, player2, seed2);
       await program.methods
-        .createUniversalMatch(
-          { highCard: {} },
-          betAmount,
-          gameConfig
-        )
+        .settleMatch(false)
         .accounts({
-          matchAccount: highCardMatchAccount.publicKey,
-          vault: highCardVault,
-          creator: player1.publicKey,
-          tokenMint: null,
+          matchAccount: match,
+          randomnessRequest: null,
+          vault: null,
+          creator: null,
           creatorTokenAccount: null,
+          tokenMint: null,
           vaultTokenAccount: null,
           tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([player1, highCardMatchAccount])
-        .rpc();
-
-      // Join and play
-      await program.methods
-        .joinMatch()
-        .accounts({
-          matchAccount: highCardMatchAccount.publicKey,
-          vault: highCardVault,
-          opponent: player2.publicKey,
-          opponentTokenAccount: null,
-          vaultTokenAccount: null,
-          tokenProgram: null,
-          systemProgram: SystemProgram.programId,
+          systemProgram: null,
         })
-        .signers([player2])
         .rpc();
 
-      // Submit card values (0-255)
-      await program.methods
-        .submitMove(Buffer.from([120])) // Card value 120
-        .accounts({
-          matchAccount: highCardMatchAccount.publicKey,
-          player: player1.publicKey,
-        })
-        .signers([player1])
-        .rpc();
-
-      await program.methods
-        .submitMove(Buffer.from([200])) // Card value 200 (higher)
-        .accounts({
-          matchAccount: highCardMatchAccount.publicKey,
-          player: player2.publicKey,
-        })
-        .signers([player2])
-        .rpc();
-
-      // Settle match
-      await program.methods
-        .settleMatch()
-        .accounts({
-          matchAccount: highCardMatchAccount.publicKey,
-        })
-        .rpc();
-
-      const match = await program.account.universalMatch.fetch(highCardMatchAccount.publicKey);
-      expect(match.status).to.deep.equal({ completed: {} });
-      expect(match.winner?.toString()).to.equal(player2.publicKey.toString()); // 200 > 120
-      expect(match.gameType).to.deep.equal({ highCard: {} });
-      
-      console.log("✅ High Card game completed - Player 2 wins with card 200 vs 120");
+      const highCardMatch = await program.account.universalMatch.fetch(match);
+      expect(highCardMatch.status).to.deep.equal({ completed: {} });
+      expect(highCardMatch.winner).to.not.be.null;
+      console.log("✅ High card dealt from both seeds and settled");
     });
   });
 
   describe("11. 0% Fees Verification & Edge Cases", () => {
     it("Should verify 0% platform fees across all game types", async () => {
       // Test with our main RPS match
-      const rpsMatch = await program.account.universalMatch.fetch(matchAccount.publicKey);
+      const rpsMatch = await program.account.universalMatch.fetch(matchAccount);
       const betAmount = rpsMatch.betAmount;
       const totalPot = rpsMatch.totalPot;
       
@@ -2228,7 +2595,7 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       console.log("✅ RPS: 0% fees verified - Total pot equals 2x bet amount");
 
       // Test with dice match
-      const diceMatch = await program.account.universalMatch.fetch(diceMatchAccount.publicKey);
+      const diceMatch = await program.account.universalMatch.fetch(diceMatchAccount);
       expect(diceMatch.totalPot.toString()).to.equal(
         diceMatch.betAmount.mul(new anchor.BN(2)).toString()
       );
@@ -2240,11 +2607,6 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
     });
 
     it("Should handle edge case: minimum bet amounts", async () => {
-      const minBetMatchAccount = Keypair.generate();
-      const [minBetVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), minBetMatchAccount.publicKey.toBuffer()],
-        program.programId
-      );
 
       const minBetAmount = new anchor.BN(10_000_000); // 0.01 SOL
       const gameConfig = {
@@ -2256,38 +2618,21 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       };
 
       // Create with minimum bet
-      await program.methods
-        .createUniversalMatch(
-          { rockPaperScissors: {} },
-          minBetAmount,
-          gameConfig
-        )
-        .accounts({
-          matchAccount: minBetMatchAccount.publicKey,
-          vault: minBetVault,
-          creator: player1.publicKey,
-          tokenMint: null,
-          creatorTokenAccount: null,
-          vaultTokenAccount: null,
-          tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([player1, minBetMatchAccount])
-        .rpc();
+      const { match: minBetMatchAccount, matchVault: minBetVault } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        minBetAmount,
+        gameConfig
+      );
+      await joinMatch(minBetMatchAccount, minBetVault, player2);
 
-      const match = await program.account.universalMatch.fetch(minBetMatchAccount.publicKey);
+      const match = await program.account.universalMatch.fetch(minBetMatchAccount);
       expect(match.totalPot.toString()).to.equal(minBetAmount.mul(new anchor.BN(2)).toString());
       
       console.log("✅ Minimum bet handling verified - 0% fees maintained");
     });
 
     it("Should handle large bet amounts", async () => {
-      const largeBetMatchAccount = Keypair.generate();
-      const [largeBetVault] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), largeBetMatchAccount.publicKey.toBuffer()],
-        program.programId
-      );
 
       const largeBetAmount = new anchor.BN(1 * LAMPORTS_PER_SOL); // 1 SOL
       const gameConfig = {
@@ -2299,52 +2644,43 @@ describe("Universal PvP Platform - Comprehensive Test Suite", () => {
       };
 
       // Create with large bet
-      await program.methods
-        .createUniversalMatch(
-          { rockPaperScissors: {} },
-          largeBetAmount,
-          gameConfig
-        )
-        .accounts({
-          matchAccount: largeBetMatchAccount.publicKey,
-          vault: largeBetVault,
-          creator: player1.publicKey,
-          tokenMint: null,
-          creatorTokenAccount: null,
-          vaultTokenAccount: null,
-          tokenProgram: null,
-          systemProgram: SystemProgram.programId,
-          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
-        })
-        .signers([player1, largeBetMatchAccount])
-        .rpc();
+      const { match: largeBetMatchAccount, matchVault: largeBetVault } = await createMatch(
+        player1,
+        { rockPaperScissors: {} },
+        largeBetAmount,
+        gameConfig
+      );
+      await joinMatch(largeBetMatchAccount, largeBetVault, player2);
 
-      const match = await program.account.universalMatch.fetch(largeBetMatchAccount.publicKey);
+      const match = await program.account.universalMatch.fetch(largeBetMatchAccount);
       expect(match.totalPot.toString()).to.equal(largeBetAmount.mul(new anchor.BN(2)).toString());
       
       console.log("✅ Large bet handling verified - 0% fees maintained at scale");
     });
 
     it("Should verify vault security and proper fund isolation", async () => {
-      // Create multiple concurrent matches to test vault isolation
-      const match1Account = Keypair.generate();
-      const match2Account = Keypair.generate();
-      
-      const [vault1] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), match1Account.publicKey.toBuffer()],
-        program.programId
-      );
-      
-      const [vault2] = PublicKey.findProgramAddressSync(
-        [Buffer.from("vault"), match2Account.publicKey.toBuffer()],
-        program.programId
+      // Two matches of the same creator get different addresses and vaults
+      const first = await createMatch(
+        player3,
+        { rockPaperScissors: {} },
+        new anchor.BN(0.05 * LAMPORTS_PER_SOL),
+        {
+          maxPlayers: 2,
+          minBet: new anchor.BN(0.01 * LAMPORTS_PER_SOL),
+          maxBet: new anchor.BN(100 * LAMPORTS_PER_SOL),
+          rounds: 1,
+          customParams: Array(16).fill(0),
+        }
       );
+      const second = await nextMatchAddresses(player3.publicKey);
 
-      // Verify different matches have different vaults
-      expect(vault1.toString()).to.not.equal(vault2.toString());
+      expect(first.match.toString()).to.not.equal(second.match.toString());
+      expect(first.matchVault.toString()).to.not.equal(second.matchVault.toString());
       
       console.log("✅ Vault isolation verified - Each match has separate vault");
       console.log("✅ SECURITY: Funds are properly isolated between matches");
+
+      await cancelMatch(first.match, first.matchVault, player3, player3.publicKey);
     });
   });
 